@@ -1,12 +1,70 @@
-use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::language::BUILTIN_LANGUAGES;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 pub struct Cli {
+    /// The directory the extensions repository is checked out into.
+    #[arg(
+        long,
+        global = true,
+        env = "EXTENSION_SURVEYOR_WORK_DIR",
+        default_value = "work"
+    )]
+    pub work_dir: PathBuf,
+    /// The URL of the extensions repository. Local repositories may be given as `file://` URLs.
+    #[arg(
+        long,
+        global = true,
+        env = "EXTENSION_SURVEYOR_REPOSITORY_URL",
+        default_value = "https://github.com/zed-industries/extensions.git"
+    )]
+    pub repository_url: String,
+    /// The revision of the extensions repository to check out (e.g., a branch, tag, or commit SHA).
+    ///
+    /// Defaults to the repository's default branch.
+    #[arg(long, global = true, env = "EXTENSION_SURVEYOR_REVISION")]
+    pub revision: Option<String>,
+    /// The format to output survey results in.
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Markdown)]
+    pub format: OutputFormat,
+    /// The file to write survey results to, instead of stdout.
+    #[arg(long, short, global = true)]
+    pub output: Option<PathBuf>,
+    /// Abort a survey on the first extension that fails to load, instead of reporting it and continuing.
+    #[arg(long, global = true)]
+    pub fail_fast: bool,
+    /// The maximum number of extensions to survey concurrently.
+    #[arg(long, short, global = true, default_value_t = 16)]
+    pub jobs: usize,
+    /// Re-parse every extension instead of reusing the index cached in the work directory.
+    #[arg(long, global = true)]
+    pub no_cache: bool,
     #[clap(subcommand)]
     pub command: Command,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// A Markdown checklist.
+    Markdown,
+    /// A single JSON document.
+    Json,
+    /// Newline-delimited JSON, with one finding per line.
+    Ndjson,
+    /// Comma-separated values, with one row per finding.
+    Csv,
+    /// Tab-separated values, with one row per finding.
+    Tsv,
+    /// A self-contained HTML page.
+    Html,
+    /// A SARIF 2.1.0 log, for use with code scanning tools.
+    Sarif,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Command {
     /// Runs a survey.
@@ -32,4 +90,44 @@ pub enum SurveyCommand {
     ExtensionJson,
     /// A survey to find which Tree-sitter grammars are in use across extensions.
     TreeSitterGrammars,
+    /// A survey of the `process:exec` capabilities declared by extensions.
+    Capabilities,
+    /// A survey that evaluates the `process:exec` capabilities declared by extensions against a policy.
+    CapabilityPolicy {
+        /// The path to the policy TOML file, listing `allow` and `deny` rules.
+        #[arg(long)]
+        policy: PathBuf,
+    },
+    /// A survey comparing the declared `process:exec` capabilities with the processes extensions actually spawn.
+    ProcessExec,
+    /// A survey of which `zed_extension_api` version each Rust extension depends on.
+    ExtensionApi,
+    /// A survey of the language servers provided by extensions.
+    LanguageServers,
+    /// A survey of the slash commands, context servers, and indexed docs providers provided by extensions.
+    Providers,
+    /// A survey checking that each extension's manifest agrees with its entry in `extensions.toml`.
+    RegistryConsistency,
+    /// A survey of the manifest schema versions in use across extensions.
+    SchemaVersions {
+        /// The minimum schema version; extensions below it are flagged.
+        #[arg(long, default_value_t = 1)]
+        min_schema_version: i32,
+    },
+    /// A survey of missing or weak metadata in extension manifests.
+    MetadataQuality,
+    /// A survey of manifest keys that Zed does not read, such as misspelled section names.
+    UnknownManifestKeys,
+    /// A survey of the debug adapters, debug locators, agent servers, and language model providers provided by extensions.
+    ExtensionPoints,
+    /// A survey of the languages provided by extensions.
+    Languages,
+    /// A survey of path suffixes and first line patterns claimed by different languages in different extensions.
+    LanguageConflicts,
+    /// A survey of language names defined by more than one extension, or that reuse a language built into Zed.
+    LanguageNames {
+        /// The name of a language built into Zed. Defaults to the languages Zed currently ships.
+        #[arg(long = "builtin-language", value_name = "NAME", default_values = BUILTIN_LANGUAGES)]
+        builtin_languages: Vec<String>,
+    },
 }
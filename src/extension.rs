@@ -1,8 +1,12 @@
 use std::collections::{BTreeMap, HashMap};
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::report::{Location, collapse_whitespace};
 
 /// The schema version of the [`ExtensionManifest`].
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Serialize, Deserialize)]
@@ -44,6 +48,234 @@ pub struct ExtensionManifest {
     pub snippets: Option<PathBuf>,
     #[serde(default)]
     pub capabilities: Vec<ExtensionCapability>,
+    #[serde(default)]
+    pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
+    #[serde(default)]
+    pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    #[serde(default)]
+    pub agent_servers: BTreeMap<Arc<str>, AgentServerManifestEntry>,
+    #[serde(default)]
+    pub language_model_providers: BTreeMap<Arc<str>, LanguageModelProviderManifestEntry>,
+}
+
+impl ExtensionManifest {
+    /// Loads the manifest for the extension in the given directory.
+    ///
+    /// Prefers `extension.toml`, falling back to the legacy `extension.json` format.
+    pub async fn load(extension_dir: &Path) -> Result<LoadedManifest, ManifestError> {
+        let toml_path = extension_dir.join(ManifestFormat::Toml.file_name());
+        if fs::try_exists(&toml_path)
+            .await
+            .map_err(|error| ManifestError::read(&toml_path, error))?
+        {
+            let contents = fs::read_to_string(&toml_path)
+                .await
+                .map_err(|error| ManifestError::read(&toml_path, error))?;
+            let manifest = toml::from_str(&contents).map_err(|error| ManifestError::Parse {
+                path: toml_path.clone(),
+                message: collapse_whitespace(error.message()),
+                location: error
+                    .span()
+                    .map(|span| Location::from_offset(&contents, span.start)),
+            })?;
+
+            return Ok(LoadedManifest {
+                manifest,
+                format: ManifestFormat::Toml,
+                path: toml_path,
+            });
+        }
+
+        let json_path = extension_dir.join(ManifestFormat::Json.file_name());
+        if fs::try_exists(&json_path)
+            .await
+            .map_err(|error| ManifestError::read(&json_path, error))?
+        {
+            let contents = fs::read_to_string(&json_path)
+                .await
+                .map_err(|error| ManifestError::read(&json_path, error))?;
+            let manifest: OldExtensionManifest =
+                serde_json_lenient::from_str(&contents).map_err(|error| ManifestError::Parse {
+                    path: json_path.clone(),
+                    message: collapse_whitespace(error.to_string().trim_end_matches(&format!(
+                        " at line {} column {}",
+                        error.line(),
+                        error.column()
+                    ))),
+                    location: (error.line() > 0).then(|| Location {
+                        line: error.line(),
+                        column: error.column(),
+                    }),
+                })?;
+            let id = extension_dir
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+
+            return Ok(LoadedManifest {
+                manifest: manifest.into_manifest(id.into()),
+                format: ManifestFormat::Json,
+                path: json_path,
+            });
+        }
+
+        Err(ManifestError::NotFound {
+            extension_dir: extension_dir.to_path_buf(),
+        })
+    }
+}
+
+/// The on-disk format of an extension manifest.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ManifestFormat {
+    /// The `extension.toml` manifest format.
+    Toml,
+    /// The legacy `extension.json` manifest format.
+    Json,
+}
+
+impl ManifestFormat {
+    /// Returns the file name of a manifest in this format.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Self::Toml => "extension.toml",
+            Self::Json => "extension.json",
+        }
+    }
+}
+
+/// An [`ExtensionManifest`] along with where it was loaded from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadedManifest {
+    pub manifest: ExtensionManifest,
+    pub format: ManifestFormat,
+    pub path: PathBuf,
+}
+
+/// An error that occurred while loading an [`ExtensionManifest`].
+#[derive(Debug)]
+pub enum ManifestError {
+    /// The extension directory contains neither an `extension.toml` nor an `extension.json`.
+    NotFound { extension_dir: PathBuf },
+    /// The manifest could not be read.
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// The manifest could not be parsed.
+    Parse {
+        path: PathBuf,
+        message: String,
+        location: Option<Location>,
+    },
+}
+
+impl ManifestError {
+    fn read(path: &Path, error: std::io::Error) -> Self {
+        Self::Read {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+
+    /// Returns the path to the manifest (or extension directory) this error pertains to.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::NotFound { extension_dir } => extension_dir,
+            Self::Read { path, .. } | Self::Parse { path, .. } => path,
+        }
+    }
+
+    /// Returns the location of the error within the manifest, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Self::Parse { location, .. } => *location,
+            Self::NotFound { .. } | Self::Read { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { extension_dir } => {
+                write!(f, "no extension manifest found in {extension_dir:?}")
+            }
+            Self::Read { path, error } => write!(f, "failed to read {path:?}: {error}"),
+            Self::Parse {
+                path,
+                message,
+                location,
+            } => {
+                write!(f, "failed to parse {path:?}")?;
+                if let Some(location) = location {
+                    write!(f, " at line {} column {}", location.line, location.column)?;
+                }
+                write!(f, ": {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read { error, .. } => Some(error),
+            Self::NotFound { .. } | Self::Parse { .. } => None,
+        }
+    }
+}
+
+/// The legacy `extension.json` manifest.
+#[derive(Debug, Deserialize)]
+struct OldExtensionManifest {
+    name: String,
+    version: Arc<str>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    repository: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    #[serde(default)]
+    themes: BTreeMap<Arc<str>, PathBuf>,
+    #[serde(default)]
+    languages: BTreeMap<Arc<str>, PathBuf>,
+    #[serde(default)]
+    grammars: BTreeMap<Arc<str>, PathBuf>,
+}
+
+impl OldExtensionManifest {
+    fn into_manifest(self, id: Arc<str>) -> ExtensionManifest {
+        ExtensionManifest {
+            id,
+            name: self.name,
+            version: self.version,
+            schema_version: SchemaVersion(0),
+            description: self.description,
+            repository: self.repository,
+            authors: self.authors,
+            lib: LibManifestEntry::default(),
+            themes: self.themes.into_values().collect(),
+            icon_themes: Vec::new(),
+            languages: self.languages.into_values().collect(),
+            grammars: self
+                .grammars
+                .into_keys()
+                .map(|grammar_name| (grammar_name, GrammarManifestEntry::default()))
+                .collect(),
+            language_servers: BTreeMap::new(),
+            context_servers: BTreeMap::new(),
+            slash_commands: BTreeMap::new(),
+            indexed_docs_providers: BTreeMap::new(),
+            snippets: None,
+            capabilities: Vec::new(),
+            debug_adapters: BTreeMap::new(),
+            debug_locators: BTreeMap::new(),
+            agent_servers: BTreeMap::new(),
+            language_model_providers: BTreeMap::new(),
+        }
+    }
 }
 
 /// A capability for an extension.
@@ -58,6 +290,9 @@ pub enum ExtensionCapability {
         /// If the last element is `**`, then any trailing arguments are allowed.
         args: Vec<String>,
     },
+    /// A capability of a kind that isn't surveyed, such as `download_file` or `npm:install`.
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -90,6 +325,29 @@ pub struct LanguageServerManifestEntry {
     languages: Vec<Arc<str>>,
     #[serde(default)]
     pub language_ids: HashMap<String, String>,
+    /// The code action kinds to request from the language server, if not the server's defaults.
+    #[serde(default)]
+    pub code_action_kinds: Option<Vec<String>>,
+}
+
+impl LanguageServerManifestEntry {
+    /// Returns the list of languages for the language server.
+    ///
+    /// Prefer this over accessing the `language` or `languages` fields directly,
+    /// as we currently support both.
+    pub fn languages(&self) -> impl Iterator<Item = Arc<str>> + '_ {
+        let language = if self.languages.is_empty() {
+            self.language.clone()
+        } else {
+            None
+        };
+        self.languages.iter().cloned().chain(language)
+    }
+
+    /// Returns whether the language server uses the deprecated `language` field.
+    pub fn uses_deprecated_language_field(&self) -> bool {
+        self.language.is_some()
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
@@ -97,9 +355,94 @@ pub struct ContextServerManifestEntry {}
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct SlashCommandManifestEntry {
+    #[serde(default)]
     pub description: String,
     pub requires_argument: bool,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct IndexedDocsProviderEntry {}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct DebugAdapterManifestEntry {
+    /// The path to the JSON schema for the debug adapter's configuration.
+    #[serde(default)]
+    pub schema_path: Option<PathBuf>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct DebugLocatorManifestEntry {}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct AgentServerManifestEntry {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// The agent server's distribution for each target platform (e.g., `darwin-aarch64`).
+    #[serde(default)]
+    pub targets: BTreeMap<String, AgentServerTargetConfig>,
+    #[serde(default)]
+    pub icon: Option<PathBuf>,
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct AgentServerTargetConfig {
+    #[serde(default)]
+    pub archive: String,
+    #[serde(default)]
+    pub cmd: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct LanguageModelProviderManifestEntry {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_capabilities() {
+        let manifest = toml::from_str::<ExtensionManifest>(
+            r#"
+            id = "example"
+            name = "Example"
+            version = "1.0.0"
+            schema_version = 1
+
+            [[capabilities]]
+            kind = "process:exec"
+            command = "node"
+            args = ["*"]
+
+            [[capabilities]]
+            kind = "download_file"
+            host = "github.com"
+            path = ["example", "**"]
+
+            [[capabilities]]
+            kind = "npm:install"
+            package = "typescript"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.capabilities,
+            [
+                ExtensionCapability::ProcessExec {
+                    command: "node".to_string(),
+                    args: vec!["*".to_string()],
+                },
+                ExtensionCapability::Other,
+                ExtensionCapability::Other,
+            ]
+        );
+    }
+}
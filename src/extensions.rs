@@ -2,9 +2,11 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::fs;
 
+use crate::extension::{ExtensionManifest, LoadedManifest, ManifestError};
+
 #[derive(Debug, Deserialize)]
 pub struct ExtensionsToml {
     #[serde(flatten)]
@@ -23,7 +25,6 @@ impl ExtensionsToml {
 #[derive(Debug, Deserialize)]
 pub struct ExtensionEntry {
     pub submodule: String,
-    #[allow(unused)]
     pub version: String,
     #[serde(default)]
     pub path: Option<String>,
@@ -38,14 +39,19 @@ impl ExtensionEntry {
 
         extension_dir
     }
+
+    /// Loads the manifest for this [`ExtensionEntry`].
+    pub async fn load_manifest(&self, root_dir: &Path) -> Result<LoadedManifest, ManifestError> {
+        ExtensionManifest::load(&self.extension_dir(root_dir)).await
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ThemeFamily {
     pub themes: Vec<Theme>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Theme {
     pub name: String,
     pub style: serde_json::Value,
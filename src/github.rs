@@ -1,9 +1,18 @@
-use anyhow::{anyhow, Result};
+use anyhow::{Result, anyhow, bail};
 use url::Url;
 
+/// Returns whether the given URL is an `http` or `https` URL, and therefore safe to link to from a
+/// report (unlike, e.g., a `javascript:` URL).
+pub fn is_web_url(url: &Url) -> bool {
+    matches!(url.scheme(), "http" | "https")
+}
+
 /// Returns a URL for creating a new GitHub Issue with the given title and body.
 pub fn create_github_issue_url(repository_url: &str, title: &str, body: &str) -> Result<Url> {
     let mut github_issue_url = Url::parse(repository_url)?;
+    if !is_web_url(&github_issue_url) {
+        bail!("invalid repository URL: expected an http or https URL");
+    }
     github_issue_url
         .path_segments_mut()
         .map_err(|_| anyhow!("invalid repository URL"))?
@@ -16,3 +25,36 @@ pub fn create_github_issue_url(repository_url: &str, title: &str, body: &str) ->
 
     Ok(github_issue_url)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_github_issue_url() {
+        let url =
+            create_github_issue_url("https://github.com/example/extension", "A title", "A body")
+                .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://github.com/example/extension/issues/new?title=A+title&body=A+body"
+        );
+    }
+
+    #[test]
+    fn test_create_github_issue_url_rejects_non_web_urls() {
+        for repository_url in [
+            "javascript://x/%0aalert(1)",
+            "javascript:alert(1)",
+            "data://text/html,<script>alert(1)</script>",
+            "file:///etc/passwd",
+            "github.com/example/extension",
+            "not a url",
+        ] {
+            assert!(
+                create_github_issue_url(repository_url, "title", "body").is_err(),
+                "{repository_url:?} should be rejected"
+            );
+        }
+    }
+}
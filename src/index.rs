@@ -0,0 +1,265 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::process::Command;
+
+use crate::extension::LoadedManifest;
+use crate::extensions::{ExtensionEntry, ThemeFamily};
+use crate::language::LanguageConfig;
+use crate::report::{Location, collapse_whitespace};
+use crate::survey::run_blocking;
+
+/// The version of the cache format.
+///
+/// This must be bumped whenever [`ExtensionIndex`] (or anything it contains) changes shape.
+const CACHE_VERSION: u32 = 8;
+
+/// The name of the directory, within the work directory, that holds the index cache.
+const CACHE_DIR_NAME: &str = ".surveyor-cache";
+
+/// The parsed contents of a single extension.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtensionIndex {
+    pub manifest: LoadedManifest,
+    /// The theme families provided by the extension.
+    ///
+    /// A theme file that can't be read or parsed is kept, with the error, so surveys can report it.
+    pub themes: Vec<IndexedFile<ThemeFamily>>,
+    /// The `config.toml` for each language provided by the extension.
+    pub languages: Vec<IndexedFile<LanguageConfig>>,
+    /// The paths of all files in the extension, relative to the extension directory.
+    pub files: Vec<PathBuf>,
+}
+
+/// A file within an extension, along with the result of parsing it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexedFile<T> {
+    pub path: PathBuf,
+    pub contents: Result<T, ParseError>,
+}
+
+/// An error that occurred while reading or parsing a file in an extension.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseError {
+    pub message: String,
+    pub location: Option<Location>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedExtensionIndex {
+    version: u32,
+    /// The commit SHA the extension's submodule was at when the index was built.
+    commit: String,
+    index: ExtensionIndex,
+}
+
+impl ExtensionIndex {
+    /// Returns the [`ExtensionIndex`] for the given extension.
+    ///
+    /// When `use_cache` is set, a previously-built index is reused as long as the extension's submodule
+    /// is still at the same commit. Otherwise, the index is rebuilt and written back to the cache.
+    pub async fn load(
+        work_dir: &Path,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+        use_cache: bool,
+    ) -> Result<Self> {
+        let commit = if use_cache {
+            submodule_commit(&work_dir.join(&extension.submodule)).await
+        } else {
+            None
+        };
+        let Some(commit) = commit else {
+            return Self::build(work_dir, extension).await;
+        };
+
+        let extension_dir = extension.extension_dir(work_dir);
+        let cache_path = work_dir
+            .join(CACHE_DIR_NAME)
+            .join(format!("{extension_id}.json"));
+        if let Ok(contents) = fs::read(&cache_path).await
+            && let Ok(cached) =
+                run_blocking(move || serde_json::from_slice::<CachedExtensionIndex>(&contents))
+                    .await?
+            && cached.version == CACHE_VERSION
+            && cached.commit == commit
+        {
+            let mut index = cached.index;
+            index.manifest.path = extension_dir.join(&index.manifest.path);
+            return Ok(index);
+        }
+
+        let mut index = Self::build(work_dir, extension).await?;
+        // The cache may be read back with a different work directory, so only store the manifest's
+        // path relative to the extension directory.
+        if let Ok(manifest_path) = index.manifest.path.strip_prefix(&extension_dir) {
+            index.manifest.path = manifest_path.to_path_buf();
+        }
+        let cached = CachedExtensionIndex {
+            version: CACHE_VERSION,
+            commit,
+            index,
+        };
+
+        let (cached, contents) = run_blocking(move || {
+            let contents = serde_json::to_vec(&cached);
+            (cached, contents)
+        })
+        .await?;
+
+        // The cache is only an optimization, so failing to write it shouldn't fail the survey.
+        if let Ok(contents) = contents
+            && fs::create_dir_all(work_dir.join(CACHE_DIR_NAME))
+                .await
+                .is_ok()
+        {
+            fs::write(&cache_path, contents).await.ok();
+        }
+
+        let mut index = cached.index;
+        index.manifest.path = extension_dir.join(&index.manifest.path);
+        Ok(index)
+    }
+
+    /// Builds the [`ExtensionIndex`] for the given extension by parsing its contents.
+    pub async fn build(work_dir: &Path, extension: &ExtensionEntry) -> Result<Self> {
+        let manifest = extension.load_manifest(work_dir).await?;
+        let extension_dir = extension.extension_dir(work_dir);
+        let files = list_files(&extension_dir).await?;
+
+        let theme_paths = if manifest.manifest.themes.is_empty() {
+            files
+                .iter()
+                .filter(|path| {
+                    path.parent() == Some(Path::new("themes"))
+                        && path
+                            .extension()
+                            .is_some_and(|extension| extension == "json")
+                })
+                .cloned()
+                .collect()
+        } else {
+            manifest.manifest.themes.clone()
+        };
+
+        let mut theme_files = Vec::new();
+        for path in theme_paths {
+            let contents = read_file(&extension_dir.join(&path)).await;
+            theme_files.push((path, contents));
+        }
+
+        let language_dirs = if manifest.manifest.languages.is_empty() {
+            files
+                .iter()
+                .filter(|path| {
+                    path.file_name().is_some_and(|name| name == "config.toml")
+                        && path.parent().and_then(Path::parent) == Some(Path::new("languages"))
+                })
+                .filter_map(|path| path.parent().map(Path::to_path_buf))
+                .collect()
+        } else {
+            manifest.manifest.languages.clone()
+        };
+
+        let mut language_files = Vec::new();
+        for language_dir in language_dirs {
+            let path = language_dir.join("config.toml");
+            let contents = read_file(&extension_dir.join(&path)).await;
+            language_files.push((path, contents));
+        }
+
+        let (themes, languages) = run_blocking(move || {
+            let themes = theme_files
+                .into_iter()
+                .map(|(path, contents)| {
+                    let contents = contents.and_then(|contents| {
+                        serde_json_lenient::from_str_lenient::<ThemeFamily>(&contents).map_err(
+                            |error| ParseError {
+                                message: collapse_whitespace(&error.to_string()),
+                                location: (error.line() > 0).then(|| Location {
+                                    line: error.line(),
+                                    column: error.column(),
+                                }),
+                            },
+                        )
+                    });
+                    IndexedFile { path, contents }
+                })
+                .collect::<Vec<_>>();
+
+            let languages = language_files
+                .into_iter()
+                .map(|(path, contents)| {
+                    let contents = contents.and_then(|contents| {
+                        toml::from_str::<LanguageConfig>(&contents).map_err(|error| ParseError {
+                            message: collapse_whitespace(error.message()),
+                            location: error
+                                .span()
+                                .map(|span| Location::from_offset(&contents, span.start)),
+                        })
+                    });
+                    IndexedFile { path, contents }
+                })
+                .collect::<Vec<_>>();
+
+            (themes, languages)
+        })
+        .await?;
+
+        Ok(Self {
+            manifest,
+            themes,
+            languages,
+            files,
+        })
+    }
+}
+
+/// Reads a file listed by an extension, which may be missing or unreadable.
+async fn read_file(path: &Path) -> Result<String, ParseError> {
+    fs::read_to_string(path).await.map_err(|error| ParseError {
+        message: format!("failed to read file: {error}"),
+        location: None,
+    })
+}
+
+/// Returns the paths of all files within the given directory, relative to it.
+async fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending_dirs = vec![PathBuf::new()];
+    while let Some(relative_dir) = pending_dirs.pop() {
+        let mut entries = fs::read_dir(dir.join(&relative_dir))
+            .await
+            .with_context(|| format!("failed to read {:?}", dir.join(&relative_dir)))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let relative_path = relative_dir.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                if entry.file_name() != ".git" {
+                    pending_dirs.push(relative_path);
+                }
+            } else {
+                files.push(relative_path);
+            }
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Returns the commit SHA checked out in the given submodule directory, if it can be determined.
+async fn submodule_commit(submodule_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(submodule_dir)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeSet;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// The names of the languages built into Zed.
+pub const BUILTIN_LANGUAGES: &[&str] = &[
+    "C",
+    "C++",
+    "CSS",
+    "Diff",
+    "Git Commit",
+    "Go",
+    "Go Mod",
+    "Go Work",
+    "JavaScript",
+    "JSDoc",
+    "JSON",
+    "JSONC",
+    "Markdown",
+    "Markdown-Inline",
+    "Plain Text",
+    "Python",
+    "Regex",
+    "Rust",
+    "Shell Script",
+    "TSX",
+    "TypeScript",
+    "YAML",
+];
+
+/// The names of the Tree-sitter grammars built into Zed.
+pub const BUILTIN_GRAMMARS: &[&str] = &[
+    "bash",
+    "c",
+    "cpp",
+    "css",
+    "diff",
+    "gitcommit",
+    "go",
+    "gomod",
+    "gowork",
+    "javascript",
+    "jsdoc",
+    "json",
+    "jsonc",
+    "markdown",
+    "markdown-inline",
+    "python",
+    "regex",
+    "rust",
+    "tsx",
+    "typescript",
+    "yaml",
+];
+
+/// The `config.toml` of a language provided by an extension.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct LanguageConfig {
+    /// The name of the language (e.g., `Rust`).
+    pub name: Arc<str>,
+    /// The name of the Tree-sitter grammar used to parse the language.
+    #[serde(default)]
+    pub grammar: Option<Arc<str>>,
+    #[serde(default)]
+    pub code_fence_block_name: Option<Arc<str>>,
+    /// The file suffixes (without the leading `.`) or file names that are associated with the language.
+    #[serde(default)]
+    pub path_suffixes: Vec<String>,
+    /// A regular expression matched against the first line of a file to detect the language.
+    #[serde(default)]
+    pub first_line_pattern: Option<String>,
+    #[serde(default)]
+    pub line_comments: Vec<Arc<str>>,
+    #[serde(default)]
+    pub block_comment: Option<BlockCommentConfig>,
+    #[serde(default)]
+    pub brackets: Vec<BracketPair>,
+    #[serde(default)]
+    pub autoclose_before: Option<String>,
+    #[serde(default)]
+    pub increase_indent_pattern: Option<String>,
+    #[serde(default)]
+    pub decrease_indent_pattern: Option<String>,
+    #[serde(default)]
+    pub tab_size: Option<NonZeroU32>,
+    #[serde(default)]
+    pub hard_tabs: Option<bool>,
+    #[serde(default)]
+    pub word_characters: BTreeSet<char>,
+    #[serde(default)]
+    pub prettier_parser_name: Option<String>,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// The delimiters of a block comment.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockCommentConfig {
+    Table {
+        start: Arc<str>,
+        end: Arc<str>,
+        #[serde(default)]
+        prefix: Option<Arc<str>>,
+        #[serde(default)]
+        tab_size: Option<u32>,
+    },
+    /// The legacy `["/*", "*/"]` format.
+    Legacy([Arc<str>; 2]),
+}
+
+/// A pair of brackets that Zed should auto-close and match.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct BracketPair {
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub close: bool,
+    #[serde(default)]
+    pub surround: bool,
+    #[serde(default)]
+    pub newline: bool,
+    /// The syntax scopes in which the bracket pair should not be auto-closed.
+    #[serde(default)]
+    pub not_in: Vec<String>,
+}
@@ -2,79 +2,150 @@ mod cli;
 mod extension;
 mod extensions;
 mod github;
+mod index;
+mod language;
+mod manifest_schema;
+mod renderer;
+mod renderers;
+mod report;
+mod repository;
 mod survey;
 mod surveys;
 
-use std::path::PathBuf;
+use std::io::Write as _;
 
 use anyhow::{Context as _, Result};
 use clap::Parser as _;
-use tokio::fs;
 
-use crate::cli::{Cli, SurveyCommand};
+use crate::cli::{Cli, OutputFormat, SurveyCommand};
 use crate::extensions::ExtensionsToml;
-use crate::survey::Survey as _;
-use crate::surveys::{ExtensionJsonUsage, ThemePropertyUsage, TreeSitterGrammars};
+use crate::renderer::Renderer;
+use crate::renderers::{
+    CsvRenderer, HtmlRenderer, JsonRenderer, MarkdownRenderer, NdjsonRenderer, SarifRenderer,
+};
+use crate::survey::{Survey as _, SurveyContext};
+use crate::surveys::{
+    Capabilities, CapabilityPolicyEvaluation, ExtensionApiVersions, ExtensionJsonUsage,
+    ExtensionPoints, LanguageConflicts, LanguageNameCollisions, LanguageServers, Languages,
+    MetadataQuality, ProcessExecUsage, ProviderInventory, RegistryConsistency, SchemaVersions,
+    ThemePropertyUsage, TreeSitterGrammars, UnknownManifestKeys,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let extension_repository_url = "https://github.com/zed-industries/extensions.git";
-    let work_dir = PathBuf::from("work");
+    let work_dir = cli.work_dir;
 
     match cli.command {
         cli::Command::Survey(survey) => {
-            let extensions_toml = ExtensionsToml::load(&work_dir).await?;
+            let cx = SurveyContext {
+                extensions_toml: ExtensionsToml::load(&work_dir).await?,
+                work_dir,
+                fail_fast: cli.fail_fast,
+                jobs: cli.jobs,
+                use_cache: !cli.no_cache,
+            };
 
-            match survey.command {
+            let report = match survey.command {
                 SurveyCommand::ThemeProperty { name } => {
                     let survey = ThemePropertyUsage::new(name);
-                    survey.run(&work_dir, &extensions_toml).await?;
-
-                    Ok(())
+                    survey.run(&cx).await?
                 }
                 SurveyCommand::ExtensionJson => {
                     let survey = ExtensionJsonUsage;
-                    survey.run(&work_dir, &extensions_toml).await?;
-
-                    Ok(())
+                    survey.run(&cx).await?
                 }
                 SurveyCommand::TreeSitterGrammars => {
                     let survey = TreeSitterGrammars;
-                    survey.run(&work_dir, &extensions_toml).await?;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::Capabilities => {
+                    let survey = Capabilities;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::CapabilityPolicy { policy } => {
+                    let survey = CapabilityPolicyEvaluation::load(&policy).await?;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::ProcessExec => {
+                    let survey = ProcessExecUsage;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::ExtensionApi => {
+                    let survey = ExtensionApiVersions;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::LanguageServers => {
+                    let survey = LanguageServers;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::Providers => {
+                    let survey = ProviderInventory;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::RegistryConsistency => {
+                    let survey = RegistryConsistency;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::SchemaVersions { min_schema_version } => {
+                    let survey = SchemaVersions::new(min_schema_version);
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::MetadataQuality => {
+                    let survey = MetadataQuality;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::UnknownManifestKeys => {
+                    let survey = UnknownManifestKeys;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::ExtensionPoints => {
+                    let survey = ExtensionPoints;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::Languages => {
+                    let survey = Languages;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::LanguageConflicts => {
+                    let survey = LanguageConflicts;
+                    survey.run(&cx).await?
+                }
+                SurveyCommand::LanguageNames { builtin_languages } => {
+                    let survey = LanguageNameCollisions::new(builtin_languages);
+                    survey.run(&cx).await?
+                }
+            };
 
-                    Ok(())
+            let renderer: Box<dyn Renderer> = match cli.format {
+                OutputFormat::Markdown => Box::new(MarkdownRenderer),
+                OutputFormat::Json => Box::new(JsonRenderer),
+                OutputFormat::Ndjson => Box::new(NdjsonRenderer),
+                OutputFormat::Csv => Box::new(CsvRenderer::csv()),
+                OutputFormat::Tsv => Box::new(CsvRenderer::tsv()),
+                OutputFormat::Html => Box::new(HtmlRenderer),
+                OutputFormat::Sarif => {
+                    Box::new(SarifRenderer::new(&cx.work_dir, &cx.extensions_toml))
                 }
-            }
-        }
-        cli::Command::UpdateRepo => {
-            if work_dir.exists() {
-                tokio::process::Command::new("git")
-                    .args(["pull", "--recurse-submodules", "--jobs=50"])
-                    .current_dir(&work_dir)
-                    .spawn()?
-                    .wait()
-                    .await
-                    .context("failed to pull extensions repository")?;
-            } else {
-                fs::create_dir_all(&work_dir).await?;
+            };
 
-                tokio::process::Command::new("git")
-                    .args([
-                        "clone",
-                        "--recurse-submodules",
-                        extension_repository_url,
-                        "--jobs=50",
-                    ])
-                    .arg(&work_dir)
-                    .spawn()?
-                    .wait()
-                    .await
-                    .context("failed to clone extensions repository")?;
+            if let Some(output) = cli.output.as_ref() {
+                let mut output_file = std::io::BufWriter::new(
+                    std::fs::File::create(output)
+                        .with_context(|| format!("failed to create {output:?}"))?,
+                );
+                renderer.render(&report, &mut output_file)?;
+                output_file.flush()?;
+            } else {
+                renderer.render(&report, &mut std::io::stdout().lock())?;
             }
 
             Ok(())
         }
+        cli::Command::UpdateRepo => {
+            repository::update_repository(&work_dir, &cli.repository_url, cli.revision.as_deref())
+                .await
+        }
     }
 }
@@ -0,0 +1,132 @@
+//! Hand-written descriptions of the keys Zed reads from extension manifests.
+//!
+//! These mirror the models in [`crate::extension`] and must be kept in sync with them.
+
+/// The expected shape of a value within an extension manifest.
+#[derive(Debug)]
+pub enum Schema {
+    /// A value whose contents are not checked.
+    Any,
+    /// A table with a fixed set of known keys.
+    Table(&'static [(&'static str, Schema)]),
+    /// A table with arbitrary keys, each of whose values follows the given schema.
+    Map(&'static Schema),
+    /// An array, each of whose elements follows the given schema.
+    Array(&'static Schema),
+}
+
+impl Schema {
+    /// Returns the schema for the given key, if this is a table containing it.
+    pub fn key(&self, key: &str) -> Option<&'static Schema> {
+        match self {
+            Self::Table(keys) => keys
+                .iter()
+                .find(|(known_key, _)| *known_key == key)
+                .map(|(_, schema)| schema),
+            Self::Map(schema) => Some(schema),
+            Self::Any | Self::Array(_) => None,
+        }
+    }
+
+    /// Returns the known keys of this schema, if it is a table with a fixed set of keys.
+    pub fn known_keys(&self) -> impl Iterator<Item = &'static str> + use<> {
+        let keys: &'static [(&'static str, Schema)] = match self {
+            Self::Table(keys) => keys,
+            Self::Any | Self::Map(_) | Self::Array(_) => &[],
+        };
+        keys.iter().map(|(key, _)| *key)
+    }
+}
+
+/// The schema of `extension.toml`.
+pub const EXTENSION_TOML: Schema = Schema::Table(&[
+    ("id", Schema::Any),
+    ("name", Schema::Any),
+    ("version", Schema::Any),
+    ("schema_version", Schema::Any),
+    ("description", Schema::Any),
+    ("repository", Schema::Any),
+    ("authors", Schema::Any),
+    (
+        "lib",
+        Schema::Table(&[("kind", Schema::Any), ("version", Schema::Any)]),
+    ),
+    ("themes", Schema::Any),
+    ("icon_themes", Schema::Any),
+    ("languages", Schema::Any),
+    (
+        "grammars",
+        Schema::Map(&Schema::Table(&[
+            ("repository", Schema::Any),
+            ("rev", Schema::Any),
+            ("commit", Schema::Any),
+            ("path", Schema::Any),
+        ])),
+    ),
+    (
+        "language_servers",
+        Schema::Map(&Schema::Table(&[
+            ("language", Schema::Any),
+            ("languages", Schema::Any),
+            ("language_ids", Schema::Any),
+            ("code_action_kinds", Schema::Any),
+        ])),
+    ),
+    ("context_servers", Schema::Map(&Schema::Table(&[]))),
+    (
+        "slash_commands",
+        Schema::Map(&Schema::Table(&[
+            ("description", Schema::Any),
+            ("requires_argument", Schema::Any),
+        ])),
+    ),
+    ("indexed_docs_providers", Schema::Map(&Schema::Table(&[]))),
+    ("snippets", Schema::Any),
+    (
+        "capabilities",
+        Schema::Array(&Schema::Table(&[
+            ("kind", Schema::Any),
+            ("command", Schema::Any),
+            ("args", Schema::Any),
+            ("host", Schema::Any),
+            ("path", Schema::Any),
+            ("package", Schema::Any),
+        ])),
+    ),
+    (
+        "debug_adapters",
+        Schema::Map(&Schema::Table(&[("schema_path", Schema::Any)])),
+    ),
+    ("debug_locators", Schema::Map(&Schema::Table(&[]))),
+    (
+        "agent_servers",
+        Schema::Map(&Schema::Table(&[
+            ("name", Schema::Any),
+            ("env", Schema::Any),
+            (
+                "targets",
+                Schema::Map(&Schema::Table(&[
+                    ("archive", Schema::Any),
+                    ("cmd", Schema::Any),
+                    ("args", Schema::Any),
+                    ("sha256", Schema::Any),
+                    ("env", Schema::Any),
+                ])),
+            ),
+            ("icon", Schema::Any),
+        ])),
+    ),
+    ("language_model_providers", Schema::Map(&Schema::Table(&[]))),
+]);
+
+/// The schema of the legacy `extension.json`.
+pub const EXTENSION_JSON: Schema = Schema::Table(&[
+    ("name", Schema::Any),
+    ("version", Schema::Any),
+    ("description", Schema::Any),
+    ("repository", Schema::Any),
+    ("authors", Schema::Any),
+    ("themes", Schema::Any),
+    ("languages", Schema::Any),
+    ("grammars", Schema::Any),
+]);
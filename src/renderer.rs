@@ -0,0 +1,9 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::report::SurveyReport;
+
+pub trait Renderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()>;
+}
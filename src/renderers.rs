@@ -0,0 +1,13 @@
+mod csv;
+mod html;
+mod json;
+mod markdown;
+mod ndjson;
+mod sarif;
+
+pub use csv::CsvRenderer;
+pub use html::HtmlRenderer;
+pub use json::JsonRenderer;
+pub use markdown::MarkdownRenderer;
+pub use ndjson::NdjsonRenderer;
+pub use sarif::SarifRenderer;
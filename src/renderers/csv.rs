@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::renderer::Renderer;
+use crate::report::SurveyReport;
+
+const COLUMNS: &[&str] = &[
+    "survey",
+    "title",
+    "extension_id",
+    "severity",
+    "message",
+    "path",
+    "line",
+    "column",
+    "repository",
+    "issue_url",
+];
+
+/// Renders the findings and errors in a [`SurveyReport`] as delimiter-separated values, with one row per finding.
+pub struct CsvRenderer {
+    delimiter: char,
+}
+
+impl CsvRenderer {
+    /// Returns a renderer that produces comma-separated values.
+    pub fn csv() -> Self {
+        Self { delimiter: ',' }
+    }
+
+    /// Returns a renderer that produces tab-separated values.
+    pub fn tsv() -> Self {
+        Self { delimiter: '\t' }
+    }
+
+    fn escape<'a>(&self, field: &'a str) -> Cow<'a, str> {
+        if self.delimiter == '\t' {
+            // TSV has no quoting, so we replace anything that would break the row structure.
+            if field.contains(['\t', '\n', '\r']) {
+                return field.replace(['\t', '\n', '\r'], " ").into();
+            }
+
+            return field.into();
+        }
+
+        if field.contains([self.delimiter, '"', '\n', '\r']) {
+            return format!("\"{}\"", field.replace('"', "\"\"")).into();
+        }
+
+        field.into()
+    }
+
+    fn write_row(&self, writer: &mut dyn Write, fields: &[&str]) -> Result<()> {
+        let row = fields
+            .iter()
+            .map(|field| self.escape(field))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter.to_string());
+        writeln!(writer, "{row}")?;
+
+        Ok(())
+    }
+}
+
+impl Renderer for CsvRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        self.write_row(writer, COLUMNS)?;
+
+        for finding in report.findings.iter().chain(&report.errors) {
+            let path = finding
+                .path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default();
+            let line = finding
+                .location
+                .map(|location| location.line.to_string())
+                .unwrap_or_default();
+            let column = finding
+                .location
+                .map(|location| location.column.to_string())
+                .unwrap_or_default();
+            let issue_url = finding
+                .issue_url
+                .as_ref()
+                .map(|url| url.as_str())
+                .unwrap_or_default();
+
+            self.write_row(
+                writer,
+                &[
+                    report.survey,
+                    &report.title,
+                    &finding.extension_id,
+                    finding.severity.as_str(),
+                    &finding.message,
+                    &path,
+                    &line,
+                    &column,
+                    finding.repository.as_deref().unwrap_or_default(),
+                    issue_url,
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+}
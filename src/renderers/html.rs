@@ -0,0 +1,309 @@
+use std::io::Write;
+
+use anyhow::Result;
+use url::Url;
+
+use crate::github;
+use crate::renderer::Renderer;
+use crate::report::{Finding, Severity, SurveyReport};
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+table { border-collapse: collapse; margin-bottom: 1.5rem; }
+th, td { border: 1px solid #d0d7de; padding: 0.25rem 0.75rem; text-align: left; }
+th { background: #f6f8fa; }
+section.extension { border: 1px solid #d0d7de; border-radius: 6px; padding: 0 1rem; margin-bottom: 1rem; }
+.severity { font-weight: 600; text-transform: uppercase; font-size: 0.8em; }
+.severity-error { color: #cf222e; }
+.severity-warning { color: #9a6700; }
+.severity-info { color: #0969da; }
+.filters { display: flex; gap: 1rem; align-items: center; margin-bottom: 1rem; }
+.hidden { display: none; }
+code { background: #f6f8fa; padding: 0 0.25rem; border-radius: 4px; }
+"#;
+
+const SCRIPT: &str = r#"
+function applyFilters() {
+  const query = document.getElementById("filter-text").value.toLowerCase();
+  const severities = new Set(
+    Array.from(document.querySelectorAll(".filter-severity:checked")).map((input) => input.value)
+  );
+  for (const section of document.querySelectorAll("section.extension")) {
+    let visibleFindings = 0;
+    for (const finding of section.querySelectorAll("li.finding")) {
+      const matches =
+        severities.has(finding.dataset.severity) &&
+        (query === "" || section.textContent.toLowerCase().includes(query));
+      finding.classList.toggle("hidden", !matches);
+      if (matches) {
+        visibleFindings += 1;
+      }
+    }
+    section.classList.toggle("hidden", visibleFindings === 0);
+  }
+  for (const row of document.querySelectorAll("tr.summary-row")) {
+    const section = document.getElementById(row.dataset.section);
+    row.classList.toggle("hidden", section.classList.contains("hidden"));
+  }
+}
+document.getElementById("filter-text").addEventListener("input", applyFilters);
+for (const input of document.querySelectorAll(".filter-severity")) {
+  input.addEventListener("change", applyFilters);
+}
+"#;
+
+const SEVERITIES: [Severity; 3] = [Severity::Error, Severity::Warning, Severity::Info];
+
+/// Renders a [`SurveyReport`] as a self-contained HTML page.
+///
+/// The page includes a summary table, a section per extension, and client-side filtering.
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        let title = escape(&report.title);
+        let extensions = report
+            .findings
+            .chunk_by(|a, b| a.extension_id == b.extension_id)
+            .collect::<Vec<_>>();
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>{title}</title>")?;
+        writeln!(writer, "<style>{STYLE}</style>")?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<section class=\"survey\">")?;
+        writeln!(writer, "<h1>{title}</h1>")?;
+
+        writeln!(writer, "<div class=\"filters\">")?;
+        writeln!(
+            writer,
+            "<input id=\"filter-text\" type=\"search\" placeholder=\"Filter extensions\">"
+        )?;
+        for severity in SEVERITIES {
+            writeln!(
+                writer,
+                "<label><input class=\"filter-severity\" type=\"checkbox\" value=\"{0}\" checked> {0}</label>",
+                severity.as_str()
+            )?;
+        }
+        writeln!(writer, "</div>")?;
+
+        writeln!(writer, "<h2>Summary</h2>")?;
+        writeln!(writer, "<table>")?;
+        write!(writer, "<tr><th>Extension</th><th>Repository</th>")?;
+        for severity in SEVERITIES {
+            write!(writer, "<th>{}</th>", severity.as_str())?;
+        }
+        writeln!(writer, "</tr>")?;
+        for findings in &extensions {
+            let extension_id = escape(&findings[0].extension_id);
+            write!(
+                writer,
+                "<tr class=\"summary-row\" data-section=\"extension-{extension_id}\"><td><a href=\"#extension-{extension_id}\"><code>{extension_id}</code></a></td><td>"
+            )?;
+            write_repository_link(writer, findings[0].repository.as_deref())?;
+            write!(writer, "</td>")?;
+            for severity in SEVERITIES {
+                let count = findings
+                    .iter()
+                    .filter(|finding| finding.severity == severity)
+                    .count();
+                write!(writer, "<td>{count}</td>")?;
+            }
+            writeln!(writer, "</tr>")?;
+        }
+        writeln!(writer, "</table>")?;
+
+        if extensions.is_empty() {
+            writeln!(writer, "<p>No findings.</p>")?;
+        }
+
+        for findings in &extensions {
+            write_extension_section(writer, findings)?;
+        }
+
+        if !report.errors.is_empty() {
+            writeln!(writer, "<h2>Errors</h2>")?;
+            writeln!(writer, "<table>")?;
+            writeln!(
+                writer,
+                "<tr><th>Extension</th><th>Error</th><th>Path</th></tr>"
+            )?;
+            for error in &report.errors {
+                let mut path = error
+                    .path
+                    .as_ref()
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_default();
+                if let Some(location) = error.location {
+                    path.push_str(&format!(":{}:{}", location.line, location.column));
+                }
+                writeln!(
+                    writer,
+                    "<tr><td><code>{}</code></td><td>{}</td><td><code>{}</code></td></tr>",
+                    escape(&error.extension_id),
+                    escape(&error.message),
+                    escape(&path)
+                )?;
+            }
+            writeln!(writer, "</table>")?;
+        }
+
+        for group in &report.groups {
+            writeln!(writer, "<h2>{}</h2>", escape(&group.title))?;
+            writeln!(writer, "<table>")?;
+            writeln!(
+                writer,
+                "<tr><th>Key</th><th>Count</th><th>Extensions</th></tr>"
+            )?;
+            for entry in &group.entries {
+                let extension_ids = entry
+                    .extension_ids
+                    .iter()
+                    .map(|extension_id| format!("<code>{}</code>", escape(extension_id)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(
+                    writer,
+                    "<tr><td>{}</td><td>{}</td><td>{extension_ids}</td></tr>",
+                    escape(&entry.key),
+                    entry.extension_ids.len()
+                )?;
+            }
+            writeln!(writer, "</table>")?;
+        }
+
+        writeln!(writer, "</section>")?;
+        writeln!(writer, "<script>{SCRIPT}</script>")?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+
+        Ok(())
+    }
+}
+
+fn write_extension_section(writer: &mut dyn Write, findings: &[Finding]) -> Result<()> {
+    let first = &findings[0];
+    let extension_id = escape(&first.extension_id);
+
+    writeln!(
+        writer,
+        "<section class=\"extension\" id=\"extension-{extension_id}\">"
+    )?;
+    writeln!(writer, "<h3><code>{extension_id}</code></h3>")?;
+
+    write!(writer, "<p>Repository: ")?;
+    write_repository_link(writer, first.repository.as_deref())?;
+    writeln!(writer, "</p>")?;
+
+    if let Some(issue_url) = findings
+        .iter()
+        .find_map(|finding| finding.issue_url.as_ref())
+    {
+        writeln!(
+            writer,
+            "<p><a href=\"{}\" target=\"_blank\" rel=\"noopener\">Create Issue</a></p>",
+            escape(issue_url.as_str())
+        )?;
+    }
+
+    writeln!(writer, "<ul>")?;
+    for finding in findings {
+        let severity = finding.severity.as_str();
+        write!(
+            writer,
+            "<li class=\"finding\" data-severity=\"{severity}\"><span class=\"severity severity-{severity}\">{severity}</span> {}",
+            escape(&finding.message)
+        )?;
+        if let Some(path) = finding.path.as_ref() {
+            write!(writer, " <code>{}", escape(&path.display().to_string()))?;
+            if let Some(location) = finding.location {
+                write!(writer, ":{}:{}", location.line, location.column)?;
+            }
+            write!(writer, "</code>")?;
+        }
+        writeln!(writer, "</li>")?;
+    }
+    writeln!(writer, "</ul>")?;
+    writeln!(writer, "</section>")?;
+
+    Ok(())
+}
+
+fn write_repository_link(writer: &mut dyn Write, repository: Option<&str>) -> Result<()> {
+    if let Some(repository) = repository {
+        // The repository comes from an untrusted manifest, so only link to it when it can't run script.
+        let is_web_url = Url::parse(repository).is_ok_and(|url| github::is_web_url(&url));
+        let repository = escape(repository);
+        if is_web_url {
+            write!(
+                writer,
+                "<a href=\"{repository}\" target=\"_blank\" rel=\"noopener\">{repository}</a>"
+            )?;
+        } else {
+            write!(writer, "<code>{repository}</code>")?;
+        }
+    } else {
+        write!(writer, "???")?;
+    }
+
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain text"), "plain text");
+        assert_eq!(
+            escape(r#"<a href="x" title='y'>&</a>"#),
+            "&lt;a href=&quot;x&quot; title=&#39;y&#39;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(escape("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn test_write_repository_link() {
+        let render = |repository| {
+            let mut output = Vec::new();
+            write_repository_link(&mut output, repository).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        assert_eq!(
+            render(Some("https://github.com/example/extension")),
+            "<a href=\"https://github.com/example/extension\" target=\"_blank\" rel=\"noopener\">https://github.com/example/extension</a>"
+        );
+        assert_eq!(
+            render(Some("javascript:alert(\"x\")")),
+            "<code>javascript:alert(&quot;x&quot;)</code>"
+        );
+        assert_eq!(
+            render(Some("javascript://x/%0aalert(1)")),
+            "<code>javascript://x/%0aalert(1)</code>"
+        );
+        assert_eq!(render(None), "???");
+    }
+}
@@ -0,0 +1,18 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::renderer::Renderer;
+use crate::report::SurveyReport;
+
+/// Renders a [`SurveyReport`] as a single JSON document.
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, report)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
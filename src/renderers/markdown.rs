@@ -0,0 +1,101 @@
+use std::io::Write;
+
+use anyhow::Result;
+use url::Url;
+
+use crate::github;
+use crate::renderer::Renderer;
+use crate::report::SurveyReport;
+
+/// Renders a [`SurveyReport`] as a Markdown checklist, with one entry per extension.
+pub struct MarkdownRenderer;
+
+impl Renderer for MarkdownRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "## {}", report.title)?;
+        writeln!(writer)?;
+
+        if report.findings.is_empty() {
+            writeln!(writer, "No findings.")?;
+        }
+
+        for findings in report
+            .findings
+            .chunk_by(|a, b| a.extension_id == b.extension_id)
+        {
+            let first = &findings[0];
+
+            writeln!(writer, "- [ ] `{}`", first.extension_id)?;
+            write!(writer, "  - Repository: ")?;
+            if let Some(repository) = first.repository.as_ref() {
+                // Like in the HTML output, only link to repositories that can't run script.
+                if Url::parse(repository).is_ok_and(|url| github::is_web_url(&url)) {
+                    writeln!(writer, "[{repository}]({repository})")?;
+                } else {
+                    writeln!(writer, "`{repository}`")?;
+                }
+            } else {
+                writeln!(writer, "???")?;
+            }
+
+            writeln!(writer, "  - Issue: TBD")?;
+            if let Some(issue_url) = findings
+                .iter()
+                .find_map(|finding| finding.issue_url.as_ref())
+            {
+                writeln!(writer, "    - [Create Issue]({issue_url})")?;
+            }
+
+            writeln!(writer, "  - Findings:")?;
+            for finding in findings {
+                write!(
+                    writer,
+                    "    - [{}] {}",
+                    finding.severity.as_str(),
+                    finding.message
+                )?;
+                if let Some(path) = finding.path.as_ref() {
+                    write!(writer, " (`{}`", path.display())?;
+                    if let Some(location) = finding.location {
+                        write!(writer, ":{}:{}", location.line, location.column)?;
+                    }
+                    write!(writer, ")")?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        if !report.errors.is_empty() {
+            writeln!(writer)?;
+            writeln!(writer, "### Errors")?;
+            writeln!(writer)?;
+
+            for error in &report.errors {
+                write!(writer, "- `{}`: {}", error.extension_id, error.message)?;
+                if let Some(path) = error.path.as_ref() {
+                    write!(writer, " (`{}`", path.display())?;
+                    if let Some(location) = error.location {
+                        write!(writer, ":{}:{}", location.line, location.column)?;
+                    }
+                    write!(writer, ")")?;
+                }
+                writeln!(writer)?;
+            }
+        }
+
+        for group in &report.groups {
+            writeln!(writer)?;
+            writeln!(writer, "### {}", group.title)?;
+            writeln!(writer)?;
+
+            for entry in &group.entries {
+                writeln!(writer, "- {} ({})", entry.key, entry.extension_ids.len())?;
+                for extension_id in &entry.extension_ids {
+                    writeln!(writer, "  - `{extension_id}`")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
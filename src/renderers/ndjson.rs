@@ -0,0 +1,84 @@
+use std::io::Write;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::renderer::Renderer;
+use crate::report::{Finding, GroupEntry, SurveyReport};
+
+/// Renders a [`SurveyReport`] as newline-delimited JSON.
+///
+/// Each finding, error, and group entry is written as its own line, tagged with the survey it came from (by identifier and title).
+pub struct NdjsonRenderer;
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Line<'a> {
+    Finding {
+        survey: &'a str,
+        title: &'a str,
+        #[serde(flatten)]
+        finding: &'a Finding,
+    },
+    Error {
+        survey: &'a str,
+        title: &'a str,
+        #[serde(flatten)]
+        error: &'a Finding,
+    },
+    GroupEntry {
+        survey: &'a str,
+        title: &'a str,
+        group: &'a str,
+        #[serde(flatten)]
+        entry: &'a GroupEntry,
+    },
+}
+
+impl Renderer for NdjsonRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        let survey = report.survey;
+        let title = report.title.as_str();
+
+        for finding in &report.findings {
+            serde_json::to_writer(
+                &mut *writer,
+                &Line::Finding {
+                    survey,
+                    title,
+                    finding,
+                },
+            )?;
+            writeln!(writer)?;
+        }
+
+        for error in &report.errors {
+            serde_json::to_writer(
+                &mut *writer,
+                &Line::Error {
+                    survey,
+                    title,
+                    error,
+                },
+            )?;
+            writeln!(writer)?;
+        }
+
+        for group in &report.groups {
+            for entry in &group.entries {
+                serde_json::to_writer(
+                    &mut *writer,
+                    &Line::GroupEntry {
+                        survey,
+                        title,
+                        group: &group.title,
+                        entry,
+                    },
+                )?;
+                writeln!(writer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
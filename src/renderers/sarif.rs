@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use url::Url;
+
+use crate::extensions::ExtensionsToml;
+use crate::renderer::Renderer;
+use crate::report::{Finding, Severity, SurveyReport};
+
+/// The rule ID used for errors that prevented an extension from being surveyed.
+const ERROR_RULE_ID: &str = "extension-error";
+
+/// The `uriBaseId` used for paths that don't belong to an extension's submodule.
+const WORK_DIR_BASE_ID: &str = "WORK_DIR";
+
+/// Renders a [`SurveyReport`] as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log.
+///
+/// File locations are relative to the submodule of the extension each finding belongs to.
+pub struct SarifRenderer {
+    work_dir: PathBuf,
+    /// The submodule path (relative to the work directory) for each extension.
+    submodules: BTreeMap<String, PathBuf>,
+}
+
+impl SarifRenderer {
+    pub fn new(work_dir: &Path, extensions_toml: &ExtensionsToml) -> Self {
+        Self {
+            work_dir: work_dir.to_path_buf(),
+            submodules: extensions_toml
+                .extensions
+                .iter()
+                .map(|(extension_id, extension)| {
+                    (extension_id.clone(), PathBuf::from(&extension.submodule))
+                })
+                .collect(),
+        }
+    }
+
+    fn base_uri(&self, relative_dir: &Path) -> Result<Url> {
+        let dir = std::path::absolute(self.work_dir.join(relative_dir))?;
+        Url::from_directory_path(&dir).map_err(|_| anyhow!("invalid directory path: {dir:?}"))
+    }
+
+    fn result(&self, finding: &Finding, base_ids: &mut BTreeMap<String, Url>) -> Result<Value> {
+        let level = match finding.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        };
+
+        let mut result = json!({
+            "level": level,
+            "message": { "text": finding.message },
+            "properties": {
+                "extensionId": finding.extension_id,
+                "repository": finding.repository,
+                "issueUrl": finding.issue_url,
+            },
+        });
+
+        if let Some(path) = finding.path.as_ref() {
+            let submodule = self
+                .submodules
+                .get(&finding.extension_id)
+                .filter(|submodule| path.starts_with(submodule));
+            let (base_id, relative_path) = match submodule {
+                Some(submodule) => {
+                    if !base_ids.contains_key(&finding.extension_id) {
+                        base_ids.insert(finding.extension_id.clone(), self.base_uri(submodule)?);
+                    }
+
+                    (finding.extension_id.as_str(), path.strip_prefix(submodule)?)
+                }
+                None => {
+                    if !base_ids.contains_key(WORK_DIR_BASE_ID) {
+                        base_ids
+                            .insert(WORK_DIR_BASE_ID.to_string(), self.base_uri(Path::new(""))?);
+                    }
+
+                    (WORK_DIR_BASE_ID, path.as_path())
+                }
+            };
+
+            let mut physical_location = json!({
+                "artifactLocation": {
+                    "uri": relative_uri(relative_path),
+                    "uriBaseId": base_id,
+                },
+            });
+            if let Some(location) = finding.location {
+                physical_location["region"] = json!({
+                    "startLine": location.line,
+                    "startColumn": location.column,
+                });
+            }
+
+            result["locations"] = json!([{ "physicalLocation": physical_location }]);
+        }
+
+        Ok(result)
+    }
+}
+
+impl Renderer for SarifRenderer {
+    fn render(&self, report: &SurveyReport, writer: &mut dyn Write) -> Result<()> {
+        let mut base_ids = BTreeMap::new();
+        let entries = report
+            .findings
+            .iter()
+            .map(|finding| (report.survey, finding))
+            .chain(report.errors.iter().map(|error| (ERROR_RULE_ID, error)));
+        let mut results = Vec::new();
+        for (rule_id, finding) in entries {
+            let mut result = self.result(finding, &mut base_ids)?;
+            result["ruleId"] = json!(rule_id);
+            results.push(result);
+        }
+
+        let sarif = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": [
+                            {
+                                "id": report.survey,
+                                "shortDescription": { "text": report.title },
+                            },
+                            {
+                                "id": ERROR_RULE_ID,
+                                "shortDescription": { "text": "Extension could not be surveyed" },
+                            },
+                        ],
+                    },
+                },
+                "originalUriBaseIds": base_ids
+                    .into_iter()
+                    .map(|(base_id, uri)| (base_id, json!({ "uri": uri })))
+                    .collect::<serde_json::Map<_, _>>(),
+                "results": results,
+            }],
+        });
+
+        serde_json::to_writer_pretty(&mut *writer, &sarif)?;
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+/// Returns a percent-encoded, relative URI reference for the given path.
+fn relative_uri(path: &Path) -> String {
+    let mut url = Url::parse("file:///").expect("valid URL");
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.extend(
+            path.components()
+                .map(|component| component.as_os_str().to_string_lossy()),
+        );
+    }
+
+    url.path().trim_start_matches('/').to_string()
+}
@@ -0,0 +1,146 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The structured result of running a [`Survey`](crate::survey::Survey).
+#[derive(Debug, Serialize)]
+pub struct SurveyReport {
+    /// The identifier of the survey that produced this report (e.g., `extension-json`).
+    pub survey: &'static str,
+    pub title: String,
+    pub findings: Vec<Finding>,
+    /// Extensions grouped by some shared key (e.g., a grammar they all provide).
+    pub groups: Vec<Group>,
+    /// Errors that prevented individual extensions from being surveyed.
+    pub errors: Vec<Finding>,
+}
+
+impl SurveyReport {
+    pub fn new(survey: &'static str, title: impl Into<String>) -> Self {
+        Self {
+            survey,
+            title: title.into(),
+            findings: Vec::new(),
+            groups: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// The severity of a [`Finding`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// A single result produced by a survey for a particular extension.
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub extension_id: String,
+    pub severity: Severity,
+    pub message: String,
+    /// The path to the file this finding pertains to, relative to the work directory.
+    pub path: Option<PathBuf>,
+    pub location: Option<Location>,
+    pub repository: Option<String>,
+    pub issue_url: Option<Url>,
+}
+
+impl Finding {
+    pub fn new(
+        extension_id: impl Into<String>,
+        severity: Severity,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            extension_id: extension_id.into(),
+            severity,
+            message: message.into(),
+            path: None,
+            location: None,
+            repository: None,
+            issue_url: None,
+        }
+    }
+
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn repository(mut self, repository: Option<&str>) -> Self {
+        self.repository = repository.map(ToString::to_string);
+        self
+    }
+
+    pub fn location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    pub fn issue_url(mut self, issue_url: Option<Url>) -> Self {
+        self.issue_url = issue_url;
+        self
+    }
+}
+
+/// A location within a file.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Location {
+    /// The 1-based line number.
+    pub line: usize,
+    /// The 1-based column number.
+    pub column: usize,
+}
+
+impl Location {
+    /// Returns the [`Location`] of the given byte offset within `text`.
+    pub fn from_offset(text: &str, offset: usize) -> Self {
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let preceding = &text[..offset];
+        let line_start = preceding.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// Collapses runs of whitespace (including newlines) in a message into single spaces.
+///
+/// Parser messages often span several lines, which breaks line-oriented output formats.
+pub fn collapse_whitespace(message: &str) -> String {
+    message.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A set of extensions grouped by a shared key.
+#[derive(Debug, Serialize)]
+pub struct Group {
+    pub title: String,
+    pub entries: Vec<GroupEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupEntry {
+    pub key: String,
+    pub extension_ids: Vec<String>,
+}
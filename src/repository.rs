@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use anyhow::{Context as _, Result, bail};
+use tokio::fs;
+use tokio::process::Command;
+
+/// Clones or updates the extensions repository in the given work directory.
+///
+/// Checks out the given `revision` (along with its submodules), or the remote's default branch if
+/// no revision is given.
+pub async fn update_repository(
+    work_dir: &Path,
+    repository_url: &str,
+    revision: Option<&str>,
+) -> Result<()> {
+    let git = Git::new(repository_url);
+    let revision = revision.unwrap_or("HEAD");
+
+    if work_dir.exists() {
+        git.run(work_dir, &["remote", "set-url", "origin", repository_url])
+            .await
+            .context("failed to set extensions repository URL")?;
+    } else {
+        fs::create_dir_all(&work_dir).await?;
+
+        let work_dir = work_dir
+            .to_str()
+            .context("work directory must be valid UTF-8")?;
+        git.run(
+            Path::new("."),
+            &["clone", "--no-checkout", repository_url, work_dir],
+        )
+        .await
+        .context("failed to clone extensions repository")?;
+    }
+
+    git.run(work_dir, &["fetch", "origin", revision])
+        .await
+        .with_context(|| format!("failed to fetch revision {revision}"))?;
+    git.run(work_dir, &["checkout", "--detach", "FETCH_HEAD"])
+        .await
+        .with_context(|| format!("failed to check out revision {revision}"))?;
+    git.run(
+        work_dir,
+        &["submodule", "update", "--init", "--recursive", "--jobs=50"],
+    )
+    .await
+    .context("failed to update submodules")?;
+
+    Ok(())
+}
+
+struct Git {
+    /// Whether submodules may be cloned from the local filesystem.
+    ///
+    /// Git disallows this by default, but it is needed to survey a local fixture checkout.
+    allow_file_protocol: bool,
+}
+
+impl Git {
+    fn new(repository_url: &str) -> Self {
+        Self {
+            allow_file_protocol: repository_url.starts_with("file://")
+                || Path::new(repository_url).exists(),
+        }
+    }
+
+    async fn run(&self, current_dir: &Path, args: &[&str]) -> Result<()> {
+        let mut command = Command::new("git");
+        if self.allow_file_protocol {
+            command.args(["-c", "protocol.file.allow=always"]);
+        }
+
+        let status = command
+            .args(args)
+            .current_dir(current_dir)
+            .spawn()?
+            .wait()
+            .await?;
+        if !status.success() {
+            bail!("`git {}` exited with {status}", args.join(" "));
+        }
+
+        Ok(())
+    }
+}
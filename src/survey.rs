@@ -1,10 +1,175 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use futures::{StreamExt as _, stream};
+use url::Url;
 
-use crate::extensions::ExtensionsToml;
+use crate::extension::ManifestError;
+use crate::extensions::{ExtensionEntry, ExtensionsToml};
+use crate::github;
+use crate::index::ExtensionIndex;
+use crate::report::{Finding, Severity, SurveyReport};
+
+/// A GitHub issue drafted for an extension's findings, to be created in its repository.
+pub struct IssueDraft {
+    pub repository: String,
+    pub title: String,
+    pub body: String,
+}
+
+/// Runs CPU-bound work, such as parsing, on the blocking thread pool.
+///
+/// The futures passed to [`SurveyContext::survey_extensions`] are all polled from the same task, so
+/// work that never yields would otherwise only ever process one extension at a time.
+pub async fn run_blocking<T: Send + 'static>(
+    work: impl FnOnce() -> T + Send + 'static,
+) -> Result<T> {
+    Ok(tokio::task::spawn_blocking(work).await?)
+}
 
 pub trait Survey {
-    async fn run(&self, work_dir: impl AsRef<Path>, extensions_toml: &ExtensionsToml)
-        -> Result<()>;
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport>;
+}
+
+/// The context in which a [`Survey`] is run.
+pub struct SurveyContext {
+    pub work_dir: PathBuf,
+    pub extensions_toml: ExtensionsToml,
+    /// Whether to abort the survey on the first per-extension error, instead of recording it in the report.
+    pub fail_fast: bool,
+    /// The maximum number of extensions to survey concurrently.
+    pub jobs: usize,
+    /// Whether to reuse extension indexes cached from previous runs.
+    pub use_cache: bool,
+}
+
+impl SurveyContext {
+    /// Returns the given path relative to the work directory, for use in a [`Finding`].
+    pub fn relative_path(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.work_dir)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+
+    /// Returns the [`ExtensionIndex`] for the given extension.
+    pub async fn extension_index(
+        &self,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<ExtensionIndex> {
+        ExtensionIndex::load(&self.work_dir, extension_id, extension, self.use_cache).await
+    }
+
+    /// Runs `survey_extension` for every extension in the registry, surveying up to
+    /// [`jobs`](Self::jobs) extensions concurrently.
+    ///
+    /// Results are returned in registry order, regardless of the order in which they complete.
+    /// Errors are recorded in the report (see [`Self::record_error`]).
+    pub async fn survey_extensions<'a, T, F, Fut>(
+        &'a self,
+        report: &mut SurveyReport,
+        survey_extension: F,
+    ) -> Result<Vec<(&'a str, T)>>
+    where
+        F: Fn(&'a str, &'a ExtensionEntry) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let survey_extension = &survey_extension;
+        let mut results = stream::iter(&self.extensions_toml.extensions)
+            .map(|(extension_id, extension)| async move {
+                (
+                    extension_id.as_str(),
+                    survey_extension(extension_id, extension).await,
+                )
+            })
+            .buffered(self.jobs.max(1));
+
+        let mut outputs = Vec::new();
+        while let Some((extension_id, result)) = results.next().await {
+            match result {
+                Ok(output) => outputs.push((extension_id, output)),
+                Err(error) => self.record_error(report, extension_id, error)?,
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Returns a URL for creating a GitHub issue in the given extension's repository.
+    ///
+    /// Repositories come from untrusted manifests, so a repository that isn't a valid URL is
+    /// recorded as an error for the extension (see [`Self::record_error`]) and yields `None`,
+    /// instead of failing the whole survey.
+    pub fn issue_url(
+        &self,
+        report: &mut SurveyReport,
+        extension_id: &str,
+        repository: &str,
+        title: &str,
+        body: &str,
+    ) -> Result<Option<Url>> {
+        match github::create_github_issue_url(repository, title, body) {
+            Ok(issue_url) => Ok(Some(issue_url)),
+            Err(error) => {
+                let error =
+                    error.context(format!("failed to create an issue URL for {repository:?}"));
+                self.record_error(report, extension_id, error)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Adds the given extension's findings to the report, linking each of them to the given issue.
+    ///
+    /// This is for surveys that draft the issue per extension while surveying, before they have
+    /// access to the report; the issue URL is created the same way as with [`Self::issue_url`].
+    pub fn push_findings(
+        &self,
+        report: &mut SurveyReport,
+        extension_id: &str,
+        mut findings: Vec<Finding>,
+        issue: Option<IssueDraft>,
+    ) -> Result<()> {
+        if let Some(issue) = issue
+            && !findings.is_empty()
+        {
+            let issue_url = self.issue_url(
+                report,
+                extension_id,
+                &issue.repository,
+                &issue.title,
+                &issue.body,
+            )?;
+            for finding in &mut findings {
+                finding.issue_url = issue_url.clone();
+            }
+        }
+        report.findings.extend(findings);
+
+        Ok(())
+    }
+
+    /// Records an error encountered while surveying the given extension in the report's errors.
+    ///
+    /// When running with `--fail-fast`, the error is returned instead.
+    pub fn record_error(
+        &self,
+        report: &mut SurveyReport,
+        extension_id: &str,
+        error: anyhow::Error,
+    ) -> Result<()> {
+        if self.fail_fast {
+            return Err(error.context(format!("failed to survey {extension_id}")));
+        }
+
+        let mut finding = Finding::new(extension_id, Severity::Error, format!("{error:#}"));
+        if let Some(manifest_error) = error.downcast_ref::<ManifestError>() {
+            finding = finding
+                .path(self.relative_path(manifest_error.path()))
+                .location(manifest_error.location());
+        }
+        report.errors.push(finding);
+
+        Ok(())
+    }
 }
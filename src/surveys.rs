@@ -1,7 +1,35 @@
+mod capabilities;
+mod capability_policy;
+mod extension_api_versions;
 mod extension_json_usage;
+mod extension_points;
+mod language_conflicts;
+mod language_names;
+mod language_servers;
+mod languages;
+mod metadata_quality;
+mod process_exec_usage;
+mod provider_inventory;
+mod registry_consistency;
+mod schema_versions;
 mod theme_property_usage;
 mod tree_sitter_grammars;
+mod unknown_manifest_keys;
 
+pub use capabilities::Capabilities;
+pub use capability_policy::CapabilityPolicyEvaluation;
+pub use extension_api_versions::ExtensionApiVersions;
 pub use extension_json_usage::ExtensionJsonUsage;
+pub use extension_points::ExtensionPoints;
+pub use language_conflicts::LanguageConflicts;
+pub use language_names::LanguageNameCollisions;
+pub use language_servers::LanguageServers;
+pub use languages::Languages;
+pub use metadata_quality::MetadataQuality;
+pub use process_exec_usage::ProcessExecUsage;
+pub use provider_inventory::ProviderInventory;
+pub use registry_consistency::RegistryConsistency;
+pub use schema_versions::SchemaVersions;
 pub use theme_property_usage::ThemePropertyUsage;
 pub use tree_sitter_grammars::TreeSitterGrammars;
+pub use unknown_manifest_keys::UnknownManifestKeys;
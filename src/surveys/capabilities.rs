@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::extension::ExtensionCapability;
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the `process:exec` capabilities declared by extensions.
+pub struct Capabilities;
+
+impl Survey for Capabilities {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("capabilities", "Process execution capabilities");
+
+        let manifests = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok(index.manifest)
+            })
+            .await?;
+
+        let mut extensions_by_command: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut extensions_with_broad_wildcards = Vec::new();
+
+        for (extension_id, loaded_manifest) in manifests {
+            let extension_manifest = loaded_manifest.manifest;
+
+            for capability in &extension_manifest.capabilities {
+                let ExtensionCapability::ProcessExec { command, args } = capability else {
+                    continue;
+                };
+
+                let command_line = std::iter::once(command)
+                    .chain(args)
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let mut message = format!("Declares `process:exec` for `{command_line}`");
+
+                let wildcard_positions = args
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, arg)| *arg == "*")
+                    .map(|(ix, _)| (ix + 1).to_string())
+                    .collect::<Vec<_>>();
+                if !wildcard_positions.is_empty() {
+                    message.push_str(&format!(
+                        "; wildcard argument at position {}",
+                        wildcard_positions.join(", ")
+                    ));
+                }
+
+                let allows_any_command = command == "*";
+                let allows_trailing_args = args.last().is_some_and(|arg| arg == "**");
+                if allows_any_command {
+                    message.push_str("; allows any command");
+                }
+                if allows_trailing_args {
+                    message.push_str("; allows any trailing arguments");
+                }
+
+                let severity = if allows_any_command || allows_trailing_args {
+                    if !extensions_with_broad_wildcards.contains(&extension_id) {
+                        extensions_with_broad_wildcards.push(extension_id);
+                    }
+
+                    Severity::Warning
+                } else {
+                    Severity::Info
+                };
+
+                report.findings.push(
+                    Finding::new(extension_id, severity, message)
+                        .path(cx.relative_path(&loaded_manifest.path))
+                        .repository(extension_manifest.repository.as_deref()),
+                );
+
+                let extension_ids = extensions_by_command.entry(command.clone()).or_default();
+                if !extension_ids.iter().any(|id| id == extension_id) {
+                    extension_ids.push(extension_id.to_string());
+                }
+            }
+        }
+
+        report.groups.push(Group {
+            title: "Extensions by command".to_string(),
+            entries: extensions_by_command
+                .into_iter()
+                .map(|(command, extension_ids)| GroupEntry {
+                    key: format!("`{command}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+        report.groups.push(Group {
+            title: "Extensions requesting broad wildcards".to_string(),
+            entries: vec![GroupEntry {
+                key: "`*` command or trailing `**` arguments".to_string(),
+                extension_ids: extensions_with_broad_wildcards
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            }],
+        });
+
+        Ok(report)
+    }
+}
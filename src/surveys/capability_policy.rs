@@ -0,0 +1,270 @@
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::extension::ExtensionCapability;
+use crate::report::{Finding, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A policy describing which `process:exec` capabilities extensions may declare.
+#[derive(Debug, Deserialize)]
+struct CapabilityPolicy {
+    /// The severity with which to report capabilities that aren't covered by any rule.
+    #[serde(default = "default_severity")]
+    default_severity: Severity,
+    #[serde(default)]
+    allow: Vec<PolicyRule>,
+    #[serde(default)]
+    deny: Vec<PolicyRule>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Warning
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicyRule {
+    /// The command this rule applies to. Use `*` to match any command.
+    command: String,
+    /// The argument patterns this rule applies to. Use `*` for a single wildcard argument.
+    /// If the last element is `**`, then any trailing arguments are matched.
+    #[serde(default)]
+    args: Vec<String>,
+    /// The severity with which to report capabilities matching this rule. Only used for `deny` rules.
+    #[serde(default)]
+    severity: Option<Severity>,
+    /// An explanation of why this rule exists.
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl PolicyRule {
+    /// Returns whether this rule covers everything the given capability allows.
+    ///
+    /// Used for `allow` rules, so that a capability is only allowed if every invocation it permits is.
+    fn covers(&self, command: &str, args: &[String]) -> bool {
+        (self.command == "*" || self.command == command) && args_match(&self.args, args)
+    }
+
+    /// Returns whether this rule matches anything the given capability allows.
+    ///
+    /// Used for `deny` rules, so that a capability is denied if any invocation it permits is.
+    fn overlaps(&self, command: &str, args: &[String]) -> bool {
+        (self.command == "*" || command == "*" || self.command == command)
+            && args_overlap(&self.args, args)
+    }
+
+    fn describe(&self) -> String {
+        std::iter::once(&self.command)
+            .chain(&self.args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Returns whether the `patterns` from a policy rule cover the (possibly wildcarded) declared `args`.
+fn args_match(patterns: &[String], args: &[String]) -> bool {
+    match (patterns, args) {
+        ([pattern], _) if pattern == "**" => true,
+        ([], []) => true,
+        ([pattern, patterns @ ..], [arg, args @ ..]) => {
+            let arg_matches = if pattern == "*" {
+                arg != "**"
+            } else {
+                pattern == arg
+            };
+
+            arg_matches && args_match(patterns, args)
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether some concrete argument list is matched by both `patterns` and the declared `args`,
+/// where either side may use `*` and a trailing `**`.
+fn args_overlap(patterns: &[String], args: &[String]) -> bool {
+    match (patterns, args) {
+        ([pattern], _) if pattern == "**" => true,
+        (_, [arg]) if arg == "**" => true,
+        ([], []) => true,
+        ([pattern, patterns @ ..], [arg, args @ ..]) => {
+            (pattern == "*" || arg == "*" || pattern == arg) && args_overlap(patterns, args)
+        }
+        _ => false,
+    }
+}
+
+/// A survey that evaluates the `process:exec` capabilities declared by extensions against a policy.
+pub struct CapabilityPolicyEvaluation {
+    policy: CapabilityPolicy,
+}
+
+impl CapabilityPolicyEvaluation {
+    /// Loads the capability policy at the given path.
+    pub async fn load(policy_path: &Path) -> Result<Self> {
+        let policy = fs::read_to_string(policy_path)
+            .await
+            .with_context(|| format!("failed to read policy file {policy_path:?}"))?;
+        let policy = toml::from_str(&policy)
+            .with_context(|| format!("failed to parse policy file {policy_path:?}"))?;
+
+        Ok(Self { policy })
+    }
+}
+
+impl Survey for CapabilityPolicyEvaluation {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("capability-policy", "Capability policy violations");
+
+        let manifests = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok(index.manifest)
+            })
+            .await?;
+
+        for (extension_id, loaded_manifest) in manifests {
+            let extension_manifest = loaded_manifest.manifest;
+
+            for capability in &extension_manifest.capabilities {
+                let ExtensionCapability::ProcessExec { command, args } = capability else {
+                    continue;
+                };
+                let command_line = std::iter::once(command)
+                    .chain(args)
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let (severity, mut message, reason) = if let Some(rule) = self
+                    .policy
+                    .deny
+                    .iter()
+                    .find(|rule| rule.overlaps(command, args))
+                {
+                    (
+                        rule.severity.unwrap_or(Severity::Error),
+                        format!(
+                            "`process:exec` for `{command_line}` is denied by policy rule `{}`",
+                            rule.describe()
+                        ),
+                        rule.reason.as_deref(),
+                    )
+                } else if self
+                    .policy
+                    .allow
+                    .iter()
+                    .any(|rule| rule.covers(command, args))
+                {
+                    continue;
+                } else {
+                    (
+                        self.policy.default_severity,
+                        format!(
+                            "`process:exec` for `{command_line}` is not allowed by any policy rule"
+                        ),
+                        None,
+                    )
+                };
+                if let Some(reason) = reason {
+                    message.push_str(&format!(" ({reason})"));
+                }
+
+                report.findings.push(
+                    Finding::new(extension_id, severity, message)
+                        .path(cx.relative_path(&loaded_manifest.path))
+                        .repository(extension_manifest.repository.as_deref()),
+                );
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn rule(command: &str, rule_args: &[&str]) -> PolicyRule {
+        PolicyRule {
+            command: command.to_string(),
+            args: args(rule_args),
+            severity: None,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_args_match() {
+        assert!(args_match(&args(&[]), &args(&[])));
+        assert!(args_match(
+            &args(&["install", "*"]),
+            &args(&["install", "foo"])
+        ));
+        assert!(args_match(
+            &args(&["install", "*"]),
+            &args(&["install", "*"])
+        ));
+        assert!(args_match(&args(&["**"]), &args(&[])));
+        assert!(args_match(&args(&["**"]), &args(&["-c", "**"])));
+        assert!(args_match(
+            &args(&["-c", "**"]),
+            &args(&["-c", "echo", "hi"])
+        ));
+
+        assert!(!args_match(&args(&[]), &args(&["foo"])));
+        assert!(!args_match(&args(&["install"]), &args(&[])));
+        assert!(!args_match(
+            &args(&["install", "foo"]),
+            &args(&["install", "*"])
+        ));
+        assert!(!args_match(&args(&["*"]), &args(&["**"])));
+        assert!(!args_match(&args(&["-c", "*"]), &args(&["-c", "a", "b"])));
+    }
+
+    #[test]
+    fn test_args_overlap() {
+        assert!(args_overlap(&args(&[]), &args(&[])));
+        assert!(args_overlap(&args(&["-c", "*"]), &args(&["**"])));
+        assert!(args_overlap(&args(&["-c", "*"]), &args(&["*", "foo"])));
+        assert!(args_overlap(&args(&["-c", "foo"]), &args(&["-c", "*"])));
+        assert!(args_overlap(&args(&["**"]), &args(&["install", "foo"])));
+        assert!(args_overlap(&args(&["install", "**"]), &args(&["install"])));
+        assert!(args_overlap(&args(&["*", "**"]), &args(&["-c", "**"])));
+
+        assert!(!args_overlap(&args(&["-c", "*"]), &args(&["-x", "**"])));
+        assert!(!args_overlap(&args(&["-c", "*"]), &args(&["-c"])));
+        assert!(!args_overlap(&args(&["-c"]), &args(&["-c", "*"])));
+        assert!(!args_overlap(&args(&[]), &args(&["*"])));
+    }
+
+    #[test]
+    fn test_deny_rules_overlap_wildcards() {
+        let deny = rule("sh", &["-c", "*"]);
+        assert!(deny.overlaps("sh", &args(&["**"])));
+        assert!(deny.overlaps("*", &args(&["-c", "echo"])));
+        assert!(!deny.overlaps("curl", &args(&["**"])));
+
+        assert!(rule("curl", &[]).overlaps("*", &args(&[])));
+        assert!(rule("*", &["**"]).overlaps("npm", &args(&["install", "*"])));
+    }
+
+    #[test]
+    fn test_allow_rules_cover_capabilities() {
+        let allow = rule("npm", &["install", "*"]);
+        assert!(allow.covers("npm", &args(&["install", "foo"])));
+        assert!(!allow.covers("npm", &args(&["install", "**"])));
+        assert!(!allow.covers("*", &args(&["install", "foo"])));
+
+        assert!(rule("sh", &["**"]).covers("sh", &args(&["-c", "**"])));
+        assert!(rule("*", &["**"]).covers("*", &args(&["**"])));
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use tokio::fs;
+
+use crate::extension::ExtensionLibraryKind;
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+const EXTENSION_API_CRATE: &str = "zed_extension_api";
+
+/// The `zed_extension_api` version used by a single extension.
+struct ExtensionApiVersion {
+    findings: Vec<Finding>,
+    /// The version to group the extension under in the distribution.
+    version: Option<String>,
+}
+
+/// A survey of which `zed_extension_api` version each Rust extension depends on.
+pub struct ExtensionApiVersions;
+
+impl ExtensionApiVersions {
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<ExtensionApiVersion> {
+        let index = cx.extension_index(extension_id, extension).await?;
+        let extension_manifest = &index.manifest.manifest;
+        let repository = extension_manifest.repository.as_deref();
+        let extension_dir = extension.extension_dir(&cx.work_dir);
+
+        // The extension builder treats any extension with a `Cargo.toml` as a Rust extension, even
+        // if it doesn't say so in its manifest.
+        let has_cargo_toml = index
+            .files
+            .iter()
+            .any(|path| path == Path::new("Cargo.toml"));
+        let is_rust = matches!(
+            extension_manifest.lib.kind,
+            Some(ExtensionLibraryKind::Rust)
+        );
+        if !is_rust && !has_cargo_toml {
+            return Ok(ExtensionApiVersion {
+                findings: Vec::new(),
+                version: None,
+            });
+        }
+
+        let mut findings = Vec::new();
+        let cargo_toml_path = extension_dir.join("Cargo.toml");
+        if !has_cargo_toml {
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Warning,
+                    "Declares a Rust library, but has no `Cargo.toml`",
+                )
+                .path(cx.relative_path(&index.manifest.path))
+                .repository(repository),
+            );
+
+            return Ok(ExtensionApiVersion {
+                findings,
+                version: None,
+            });
+        }
+
+        let cargo_toml: toml::Table = toml::from_str(&read_file(&cargo_toml_path).await?)
+            .with_context(|| format!("failed to parse {cargo_toml_path:?}"))?;
+        let Some(dependency) = cargo_toml
+            .get("dependencies")
+            .and_then(|dependencies| dependencies.get(EXTENSION_API_CRATE))
+        else {
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Warning,
+                    format!("Does not depend on `{EXTENSION_API_CRATE}`"),
+                )
+                .path(cx.relative_path(&cargo_toml_path))
+                .repository(repository),
+            );
+
+            return Ok(ExtensionApiVersion {
+                findings,
+                version: None,
+            });
+        };
+
+        let requirement = describe_dependency(dependency);
+
+        // The lockfile may live at the root of the submodule, if the extension is part of a workspace.
+        let cargo_lock_path = [
+            extension_dir.join("Cargo.lock"),
+            cx.work_dir.join(&extension.submodule).join("Cargo.lock"),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+        let locked_version = match cargo_lock_path.as_ref() {
+            Some(cargo_lock_path) => locked_version(cargo_lock_path).await?,
+            None => None,
+        };
+
+        let mut message = format!("Depends on `{EXTENSION_API_CRATE}` {requirement}");
+        match locked_version.as_ref() {
+            Some(locked_version) => message.push_str(&format!(" (locked to {locked_version})")),
+            None => message.push_str(" (no lockfile entry)"),
+        }
+        findings.push(
+            Finding::new(extension_id, Severity::Info, message)
+                .path(cx.relative_path(&cargo_toml_path))
+                .repository(repository),
+        );
+
+        if let Some(lib_version) = extension_manifest.lib.version.as_ref() {
+            let problem = match locked_version.as_ref() {
+                Some(locked_version) => (locked_version != lib_version).then(|| {
+                    (
+                        Severity::Warning,
+                        format!(
+                            "`lib.version` is {lib_version}, but `{EXTENSION_API_CRATE}` is locked to {locked_version}"
+                        ),
+                    )
+                }),
+                // Without a lockfile entry, the best we can do is check `lib.version` against the
+                // requirement in the `Cargo.toml`.
+                None => match check_requirement(dependency, lib_version) {
+                    Some(true) => None,
+                    Some(false) => Some((
+                        Severity::Warning,
+                        format!(
+                            "`lib.version` is {lib_version}, which doesn't satisfy the `{EXTENSION_API_CRATE}` requirement {requirement}"
+                        ),
+                    )),
+                    None => Some((
+                        Severity::Info,
+                        format!(
+                            "`lib.version` is {lib_version}, but it couldn't be checked against `{EXTENSION_API_CRATE}` {requirement} without a lockfile entry"
+                        ),
+                    )),
+                },
+            };
+
+            if let Some((severity, message)) = problem {
+                findings.push(
+                    Finding::new(extension_id, severity, message)
+                        .path(cx.relative_path(&index.manifest.path))
+                        .repository(repository),
+                );
+            }
+        }
+
+        Ok(ExtensionApiVersion {
+            findings,
+            version: Some(locked_version.unwrap_or(requirement)),
+        })
+    }
+}
+
+impl Survey for ExtensionApiVersions {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("extension-api", "`zed_extension_api` versions");
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+
+        // Also keyed by the raw version, as descriptions like "from the workspace" have no
+        // numeric sort key and would otherwise all be merged together.
+        let mut extensions_by_version: BTreeMap<(Vec<u64>, String), Vec<String>> = BTreeMap::new();
+        for (extension_id, result) in results {
+            report.findings.extend(result.findings);
+
+            if let Some(version) = result.version {
+                extensions_by_version
+                    .entry((version_sort_key(&version), version))
+                    .or_default()
+                    .push(extension_id.to_string());
+            }
+        }
+
+        report.groups.push(Group {
+            title: format!("Extensions by `{EXTENSION_API_CRATE}` version"),
+            entries: extensions_by_version
+                .into_iter()
+                .map(|((_, version), extension_ids)| GroupEntry {
+                    key: version,
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
+
+async fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .await
+        .with_context(|| format!("failed to read {path:?}"))
+}
+
+/// Returns a human-readable description of a dependency specification from a `Cargo.toml`.
+fn describe_dependency(dependency: &toml::Value) -> String {
+    match dependency {
+        toml::Value::String(version) => version.clone(),
+        toml::Value::Table(table) => {
+            if let Some(version) = table.get("version").and_then(toml::Value::as_str) {
+                version.to_string()
+            } else if let Some(git) = table.get("git").and_then(toml::Value::as_str) {
+                format!("from {git}")
+            } else if let Some(path) = table.get("path").and_then(toml::Value::as_str) {
+                format!("from path {path}")
+            } else if table.contains_key("workspace") {
+                "from the workspace".to_string()
+            } else {
+                "with an unknown version".to_string()
+            }
+        }
+        _ => "with an unknown version".to_string(),
+    }
+}
+
+/// Returns whether the given version satisfies the version requirement of a dependency
+/// specification from a `Cargo.toml`, or `None` if either can't be parsed.
+fn check_requirement(dependency: &toml::Value, version: &str) -> Option<bool> {
+    let requirement = match dependency {
+        toml::Value::String(requirement) => requirement.as_str(),
+        toml::Value::Table(table) => table.get("version")?.as_str()?,
+        _ => return None,
+    };
+    let requirement = semver::VersionReq::parse(requirement).ok()?;
+    let version = semver::Version::parse(version).ok()?;
+
+    Some(requirement.matches(&version))
+}
+
+/// Returns the version of `zed_extension_api` locked in the given `Cargo.lock`.
+async fn locked_version(cargo_lock_path: &Path) -> Result<Option<String>> {
+    let cargo_lock: toml::Table = toml::from_str(&read_file(cargo_lock_path).await?)
+        .with_context(|| format!("failed to parse {cargo_lock_path:?}"))?;
+
+    let versions = cargo_lock
+        .get("package")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|package| {
+            package.get("name").and_then(toml::Value::as_str) == Some(EXTENSION_API_CRATE)
+        })
+        .filter_map(|package| package.get("version").and_then(toml::Value::as_str))
+        .collect::<Vec<_>>();
+
+    Ok((!versions.is_empty()).then(|| versions.join(", ")))
+}
+
+/// Returns a key that sorts version strings numerically (so `0.10.0` sorts after `0.2.0`).
+fn version_sort_key(version: &str) -> Vec<u64> {
+    version
+        .split(|char: char| !char.is_ascii_digit())
+        .filter_map(|part| part.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_requirement() {
+        let dependency =
+            |source: &str| source.parse::<toml::Table>().unwrap()["dependency"].clone();
+
+        assert_eq!(
+            check_requirement(&dependency(r#"dependency = "0.1""#), "0.1.0"),
+            Some(true)
+        );
+        assert_eq!(
+            check_requirement(&dependency(r#"dependency = "0.2.0""#), "0.1.0"),
+            Some(false)
+        );
+        assert_eq!(
+            check_requirement(
+                &dependency(r#"dependency = { version = "=0.1.0" }"#),
+                "0.1.0"
+            ),
+            Some(true)
+        );
+        assert_eq!(
+            check_requirement(
+                &dependency(r#"dependency = { git = "https://example.com" }"#),
+                "0.1.0"
+            ),
+            None
+        );
+        assert_eq!(
+            check_requirement(&dependency(r#"dependency = "0.1""#), "not a version"),
+            None
+        );
+    }
+}
@@ -1,66 +1,66 @@
-use std::io::Write;
-use std::path::Path;
+use anyhow::Result;
 
-use anyhow::{Context as _, Result};
-use serde::Deserialize;
-use tokio::fs;
-
-use crate::extensions::ExtensionsToml;
-use crate::github;
-use crate::survey::Survey;
-
-#[derive(Debug, Deserialize)]
-struct MinimalExtensionManifest {
-    pub repository: Option<String>,
-}
+use crate::extension::ManifestFormat;
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Severity, SurveyReport};
+use crate::survey::{IssueDraft, Survey, SurveyContext};
 
 pub struct ExtensionJsonUsage;
 
-impl Survey for ExtensionJsonUsage {
-    async fn run(
+impl ExtensionJsonUsage {
+    async fn survey_extension(
         &self,
-        work_dir: impl AsRef<Path>,
-        extensions_toml: &ExtensionsToml,
-    ) -> Result<()> {
-        let work_dir = work_dir.as_ref();
-        let mut report = Vec::new();
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<(Vec<Finding>, Option<IssueDraft>)> {
+        let loaded_manifest = cx.extension_index(extension_id, extension).await?.manifest;
+        if loaded_manifest.format != ManifestFormat::Json {
+            return Ok((Vec::new(), None));
+        }
 
-        writeln!(report, "## Extensions using `extension.json`")?;
+        let extension_manifest = loaded_manifest.manifest;
 
-        for (extension_id, extension) in &extensions_toml.extensions {
-            let extension_json_path = extension.extension_dir(work_dir).join("extension.json");
-            if !extension_json_path.exists() {
-                continue;
-            }
+        let issue = extension_manifest.repository.as_ref().map(|repository| {
+            const ZED_DOCS_URL: &str = "https://zed.dev/docs/extensions/developing-extensions#directory-structure-of-a-zed-extension";
 
-            let extension_manifest: MinimalExtensionManifest =
-                serde_json_lenient::from_str(&fs::read_to_string(&extension_json_path).await?)
-                    .with_context(|| format!("failed to read manifest for {extension_id}"))?;
+            let title = "Migrate to `extension.toml`".to_string();
+            let mut body = String::new();
+            body.push_str("This extension has been identified as still using the legacy `extension.json` manifest format.\n\n");
+            body.push_str(&format!("Extensions should use the new `extension.toml` manifest format. See the [Zed extension documentation]({ZED_DOCS_URL}) for more information."));
 
-            writeln!(report, "- [ ] `{extension_id}`")?;
-            if let Some(repository) = extension_manifest.repository.as_ref() {
-                writeln!(report, "  - Repository URL: [{repository}]({repository})")?;
-            } else {
-                writeln!(report, "???")?;
+            IssueDraft {
+                repository: repository.clone(),
+                title,
+                body,
             }
+        });
 
-            writeln!(report, "  - Issue: ")?;
+        let finding = Finding::new(
+            extension_id,
+            Severity::Warning,
+            "Uses the legacy `extension.json` manifest format",
+        )
+        .path(cx.relative_path(&loaded_manifest.path))
+        .repository(extension_manifest.repository.as_deref());
 
-            if let Some(repository) = extension_manifest.repository.as_ref() {
-                const ZED_DOCS_URL: &str = "https://zed.dev/docs/extensions/developing-extensions#directory-structure-of-a-zed-extension";
+        Ok((vec![finding], issue))
+    }
+}
 
-                let title = "Migrate to `extension.toml`";
-                let mut body = String::new();
-                body.push_str("This extension has been identified as still using the legacy `extension.json` manifest format.\n\n");
-                body.push_str(&format!("Extensions should use the new `extension.toml` manifest format. See the [Zed extension documentation]({ZED_DOCS_URL}) for more information."));
+impl Survey for ExtensionJsonUsage {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("extension-json", "Extensions using `extension.json`");
 
-                let github_issue_url = github::create_github_issue_url(repository, title, &body)?;
-                writeln!(report, "    - [Create Issue]({github_issue_url})")?;
-            }
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+        for (extension_id, (findings, issue)) in results {
+            cx.push_findings(&mut report, extension_id, findings, issue)?;
         }
 
-        println!("{}", String::from_utf8_lossy(&report));
-
-        Ok(())
+        Ok(report)
     }
 }
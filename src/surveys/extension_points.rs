@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the debug adapters, debug locators, agent servers, and language model providers provided by extensions.
+pub struct ExtensionPoints;
+
+impl Survey for ExtensionPoints {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new(
+            "extension-points",
+            "Debug adapters, debug locators, agent servers, and language model providers",
+        );
+
+        let indexes = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                cx.extension_index(extension_id, extension)
+            })
+            .await?;
+
+        let mut extensions_by_extension_point: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let mut extensions_by_agent_server_target: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+        for (extension_id, index) in &indexes {
+            let loaded_manifest = &index.manifest;
+            let extension_manifest = &loaded_manifest.manifest;
+            let manifest_path = cx.relative_path(&loaded_manifest.path);
+            let repository = extension_manifest.repository.as_deref();
+            let finding = |severity, message| {
+                Finding::new(*extension_id, severity, message)
+                    .path(manifest_path.clone())
+                    .repository(repository)
+            };
+            let has_file = |path: &Path| index.files.contains(&normalize(path));
+
+            let extension_points = [
+                (
+                    "debug_adapters",
+                    !extension_manifest.debug_adapters.is_empty(),
+                ),
+                (
+                    "debug_locators",
+                    !extension_manifest.debug_locators.is_empty(),
+                ),
+                (
+                    "agent_servers",
+                    !extension_manifest.agent_servers.is_empty(),
+                ),
+                (
+                    "language_model_providers",
+                    !extension_manifest.language_model_providers.is_empty(),
+                ),
+            ];
+            for (extension_point, is_used) in extension_points {
+                if is_used {
+                    extensions_by_extension_point
+                        .entry(extension_point)
+                        .or_default()
+                        .push(extension_id.to_string());
+                }
+            }
+
+            for (adapter_name, adapter) in &extension_manifest.debug_adapters {
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!("Provides debug adapter `{adapter_name}`"),
+                ));
+
+                if let Some(schema_path) = adapter.schema_path.as_deref()
+                    && !has_file(schema_path)
+                {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!(
+                            "Debug adapter `{adapter_name}` has a `schema_path` that does not exist: `{}`",
+                            schema_path.display()
+                        ),
+                    ));
+                }
+            }
+
+            for locator_name in extension_manifest.debug_locators.keys() {
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!("Provides debug locator `{locator_name}`"),
+                ));
+            }
+
+            for (server_name, server) in &extension_manifest.agent_servers {
+                let targets = server
+                    .targets
+                    .keys()
+                    .map(|target| format!("`{target}`"))
+                    .collect::<Vec<_>>();
+                report.findings.push(finding(
+                    Severity::Info,
+                    if targets.is_empty() {
+                        format!("Provides agent server `{server_name}`")
+                    } else {
+                        format!(
+                            "Provides agent server `{server_name}` for {}",
+                            targets.join(", ")
+                        )
+                    },
+                ));
+
+                if server.targets.is_empty() {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!("Agent server `{server_name}` does not declare any targets"),
+                    ));
+                }
+
+                for (target_name, target) in &server.targets {
+                    extensions_by_agent_server_target
+                        .entry(target_name)
+                        .or_default()
+                        .push(extension_id.to_string());
+
+                    for (field, value) in [("archive", &target.archive), ("cmd", &target.cmd)] {
+                        if value.trim().is_empty() {
+                            report.findings.push(finding(
+                                Severity::Warning,
+                                format!(
+                                    "Agent server `{server_name}` target `{target_name}` is missing `{field}`"
+                                ),
+                            ));
+                        }
+                    }
+                }
+
+                if let Some(icon) = server.icon.as_deref()
+                    && !has_file(icon)
+                {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!(
+                            "Agent server `{server_name}` has an `icon` that does not exist: `{}`",
+                            icon.display()
+                        ),
+                    ));
+                }
+            }
+
+            for provider_name in extension_manifest.language_model_providers.keys() {
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!("Provides language model provider `{provider_name}`"),
+                ));
+            }
+        }
+
+        report.groups.push(Group {
+            title: "Extensions by extension point".to_string(),
+            entries: extensions_by_extension_point
+                .into_iter()
+                .map(|(extension_point, extension_ids)| GroupEntry {
+                    key: format!("`{extension_point}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+        report.groups.push(Group {
+            title: "Extensions by agent server target".to_string(),
+            entries: extensions_by_agent_server_target
+                .into_iter()
+                .map(|(target_name, mut extension_ids)| {
+                    extension_ids.dedup();
+                    GroupEntry {
+                        key: format!("`{target_name}`"),
+                        extension_ids,
+                    }
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
+
+/// Normalizes a manifest-relative path for comparison against the extension's file listing.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
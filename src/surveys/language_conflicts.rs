@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::language::LanguageConfig;
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// Words that are too common in first line patterns (e.g., in shebangs) to indicate an overlap.
+const COMMON_FIRST_LINE_WORDS: &[&str] = &["bin", "env", "local", "sbin", "usr"];
+
+/// A survey of path suffixes and first line patterns claimed by different languages in different extensions.
+pub struct LanguageConflicts;
+
+/// A language provided by an extension.
+struct ExtensionLanguage<'a> {
+    extension_id: &'a str,
+    repository: Option<&'a str>,
+    /// The path to the language's `config.toml`, relative to the work directory.
+    config_path: PathBuf,
+    config: &'a LanguageConfig,
+}
+
+impl ExtensionLanguage<'_> {
+    fn finding(&self, message: String) -> Finding {
+        Finding::new(self.extension_id, Severity::Warning, message)
+            .path(self.config_path.clone())
+            .repository(self.repository)
+    }
+
+    fn conflicts_with(&self, other: &Self) -> bool {
+        self.extension_id != other.extension_id && self.config.name != other.config.name
+    }
+}
+
+impl Survey for LanguageConflicts {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("language-conflicts", "Language conflicts");
+
+        let indexes = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok((index, extension.extension_dir(&cx.work_dir)))
+            })
+            .await?;
+
+        let languages = indexes
+            .iter()
+            .flat_map(|(extension_id, (index, extension_dir))| {
+                index.languages.iter().filter_map(|language| {
+                    Some(ExtensionLanguage {
+                        extension_id,
+                        repository: index.manifest.manifest.repository.as_deref(),
+                        config_path: cx.relative_path(&extension_dir.join(&language.path)),
+                        config: language.contents.as_ref().ok()?,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut languages_by_path_suffix: BTreeMap<&str, Vec<&ExtensionLanguage>> = BTreeMap::new();
+        for language in &languages {
+            for path_suffix in &language.config.path_suffixes {
+                languages_by_path_suffix
+                    .entry(path_suffix)
+                    .or_default()
+                    .push(language);
+            }
+        }
+
+        let mut path_suffix_conflicts = Vec::new();
+        for (path_suffix, claimants) in languages_by_path_suffix {
+            let mut extension_ids = BTreeSet::new();
+            for language in &claimants {
+                let others = claimants
+                    .iter()
+                    .filter(|other| language.conflicts_with(other))
+                    .map(|other| format!("`{}` (`{}`)", other.config.name, other.extension_id))
+                    .collect::<Vec<_>>();
+                if others.is_empty() {
+                    continue;
+                }
+
+                report.findings.push(language.finding(format!(
+                    "Path suffix `{path_suffix}` of language `{}` is also claimed by {}",
+                    language.config.name,
+                    others.join(", ")
+                )));
+                extension_ids.insert(language.extension_id.to_string());
+            }
+
+            if !extension_ids.is_empty() {
+                path_suffix_conflicts.push(GroupEntry {
+                    key: format!("`{path_suffix}`"),
+                    extension_ids: extension_ids.into_iter().collect(),
+                });
+            }
+        }
+
+        let mut first_line_pattern_conflicts: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let patterns = languages
+            .iter()
+            .filter_map(|language| {
+                let pattern = language.config.first_line_pattern.as_deref()?;
+                Some((language, pattern, first_line_words(pattern)))
+            })
+            .collect::<Vec<_>>();
+        for (language, pattern, words) in &patterns {
+            for (other, other_pattern, other_words) in &patterns {
+                if !language.conflicts_with(other) {
+                    continue;
+                }
+
+                let (overlap, keys) = if pattern == other_pattern {
+                    ("is identical to".to_string(), vec![format!("{pattern:?}")])
+                } else {
+                    let shared_words = words
+                        .intersection(other_words)
+                        .map(|word| format!("`{word}`"))
+                        .collect::<Vec<_>>();
+                    if shared_words.is_empty() {
+                        continue;
+                    }
+                    (
+                        format!("shares {} with", shared_words.join(", ")),
+                        shared_words,
+                    )
+                };
+
+                report.findings.push(language.finding(format!(
+                    "First line pattern {pattern:?} of language `{}` {overlap} that of `{}` (`{}`): {other_pattern:?}",
+                    language.config.name, other.config.name, other.extension_id
+                )));
+                for key in keys {
+                    first_line_pattern_conflicts
+                        .entry(key)
+                        .or_default()
+                        .insert(language.extension_id.to_string());
+                }
+            }
+        }
+
+        // Keep each extension's findings together, as they were produced per conflict rather than per extension.
+        report
+            .findings
+            .sort_by(|a, b| a.extension_id.cmp(&b.extension_id));
+
+        report.groups.push(Group {
+            title: "Path suffixes claimed by multiple extensions".to_string(),
+            entries: path_suffix_conflicts,
+        });
+        report.groups.push(Group {
+            title: "Overlapping first line patterns".to_string(),
+            entries: first_line_pattern_conflicts
+                .into_iter()
+                .map(|(overlap, extension_ids)| GroupEntry {
+                    key: overlap,
+                    extension_ids: extension_ids.into_iter().collect(),
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
+
+/// Returns the literal words in the given first line pattern, ignoring regex escapes and common words.
+///
+/// Two patterns sharing a word (e.g., `python`) are likely to match some of the same files.
+fn first_line_words(pattern: &str) -> BTreeSet<String> {
+    let mut words = BTreeSet::new();
+    let mut word = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c.is_alphabetic() {
+            word.push(c.to_ascii_lowercase());
+            continue;
+        }
+
+        words.insert(std::mem::take(&mut word));
+        if c == '\\' {
+            chars.next();
+        }
+    }
+    words.insert(word);
+
+    words.retain(|word| word.len() >= 3 && !COMMON_FIRST_LINE_WORDS.contains(&word.as_str()));
+    words
+}
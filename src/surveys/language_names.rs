@@ -0,0 +1,134 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of language names defined by more than one extension, or that reuse a language built into Zed.
+pub struct LanguageNameCollisions {
+    builtin_languages: BTreeSet<String>,
+}
+
+impl LanguageNameCollisions {
+    pub fn new(builtin_languages: Vec<String>) -> Self {
+        Self {
+            builtin_languages: builtin_languages.into_iter().collect(),
+        }
+    }
+}
+
+/// A definition of a language by an extension.
+struct LanguageDefinition<'a> {
+    extension_id: &'a str,
+    grammar: Option<&'a str>,
+    repository: Option<&'a str>,
+    /// The path to the language's `config.toml`, relative to the work directory.
+    config_path: PathBuf,
+}
+
+impl Survey for LanguageNameCollisions {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("language-names", "Language name collisions");
+
+        let indexes = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok((index, extension.extension_dir(&cx.work_dir)))
+            })
+            .await?;
+
+        let mut definitions_by_name: BTreeMap<&str, Vec<LanguageDefinition>> = BTreeMap::new();
+        for (extension_id, (index, extension_dir)) in &indexes {
+            for language in &index.languages {
+                let Ok(config) = &language.contents else {
+                    continue;
+                };
+
+                definitions_by_name
+                    .entry(&config.name)
+                    .or_default()
+                    .push(LanguageDefinition {
+                        extension_id,
+                        grammar: config.grammar.as_deref(),
+                        repository: index.manifest.manifest.repository.as_deref(),
+                        config_path: cx.relative_path(&extension_dir.join(&language.path)),
+                    });
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (language_name, definitions) in definitions_by_name {
+            let is_builtin = self.builtin_languages.contains(language_name);
+            if definitions.len() < 2 && !is_builtin {
+                continue;
+            }
+
+            for (ix, definition) in definitions.iter().enumerate() {
+                let mut collisions = definitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_ix, _)| *other_ix != ix)
+                    .map(|(_, other)| {
+                        format!(
+                            "`{}` ({})",
+                            other.extension_id,
+                            describe_grammar(other.grammar)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                if is_builtin {
+                    collisions.insert(0, "Zed itself".to_string());
+                }
+
+                report.findings.push(
+                    Finding::new(
+                        definition.extension_id,
+                        Severity::Warning,
+                        format!(
+                            "Language `{language_name}` ({}) is also defined by {}",
+                            describe_grammar(definition.grammar),
+                            collisions.join(", ")
+                        ),
+                    )
+                    .path(definition.config_path.clone())
+                    .repository(definition.repository),
+                );
+            }
+
+            let mut extensions_by_grammar: BTreeMap<Option<&str>, Vec<String>> = BTreeMap::new();
+            for definition in &definitions {
+                let extension_ids = extensions_by_grammar.entry(definition.grammar).or_default();
+                if extension_ids.last().map(String::as_str) != Some(definition.extension_id) {
+                    extension_ids.push(definition.extension_id.to_string());
+                }
+            }
+            for (grammar, extension_ids) in extensions_by_grammar {
+                let mut key = format!("`{language_name}` ({})", describe_grammar(grammar));
+                if is_builtin {
+                    key.push_str(", built into Zed");
+                }
+                entries.push(GroupEntry { key, extension_ids });
+            }
+        }
+
+        report
+            .findings
+            .sort_by(|a, b| a.extension_id.cmp(&b.extension_id));
+
+        report.groups.push(Group {
+            title: "Colliding language names by grammar".to_string(),
+            entries,
+        });
+
+        Ok(report)
+    }
+}
+
+fn describe_grammar(grammar: Option<&str>) -> String {
+    match grammar {
+        Some(grammar) => format!("grammar `{grammar}`"),
+        None => "no grammar".to_string(),
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+
+use crate::language::BUILTIN_LANGUAGES;
+use crate::report::{Finding, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the language servers provided by extensions.
+pub struct LanguageServers;
+
+impl Survey for LanguageServers {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("language-servers", "Language servers");
+
+        let indexes = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                cx.extension_index(extension_id, extension)
+            })
+            .await?;
+
+        let known_languages = indexes
+            .iter()
+            .flat_map(|(_, index)| &index.languages)
+            .filter_map(|language| language.contents.as_ref().ok())
+            .map(|config| config.name.as_ref())
+            .chain(BUILTIN_LANGUAGES.iter().copied())
+            .collect::<BTreeSet<_>>();
+
+        for (extension_id, index) in &indexes {
+            let extension_manifest = &index.manifest.manifest;
+            let manifest_path = cx.relative_path(&index.manifest.path);
+            let repository = extension_manifest.repository.as_deref();
+            let finding = |severity, message| {
+                Finding::new(*extension_id, severity, message)
+                    .path(manifest_path.clone())
+                    .repository(repository)
+            };
+
+            for (server_name, server) in &extension_manifest.language_servers {
+                let languages = server.languages().collect::<Vec<_>>();
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!(
+                        "Language server `{server_name}` for {}",
+                        languages
+                            .iter()
+                            .map(|language| format!("`{language}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ));
+
+                if server.uses_deprecated_language_field() {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!(
+                            "Language server `{server_name}` uses the deprecated `language` field instead of `languages`"
+                        ),
+                    ));
+                }
+
+                if languages.is_empty() {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!("Language server `{server_name}` does not target any languages"),
+                    ));
+                }
+
+                for language in &languages {
+                    if !known_languages.contains(language.as_ref()) {
+                        report.findings.push(finding(
+                            Severity::Warning,
+                            format!(
+                                "Language server `{server_name}` targets `{language}`, which is not defined by any extension or built into Zed"
+                            ),
+                        ));
+                    }
+                }
+
+                for language in server.language_ids.keys() {
+                    if !known_languages.contains(language.as_str()) {
+                        report.findings.push(finding(
+                            Severity::Warning,
+                            format!(
+                                "Language server `{server_name}` has a `language_ids` entry for `{language}`, which is not a known language"
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the languages provided by extensions.
+pub struct Languages;
+
+impl Languages {
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<(Vec<Finding>, Vec<String>)> {
+        let index = cx.extension_index(extension_id, extension).await?;
+        let repository = index.manifest.manifest.repository.as_deref();
+        let extension_dir = extension.extension_dir(&cx.work_dir);
+
+        let mut findings = Vec::new();
+        let mut language_names = Vec::new();
+
+        for language in &index.languages {
+            let config_path = cx.relative_path(&extension_dir.join(&language.path));
+            let config = match &language.contents {
+                Ok(config) => config,
+                Err(err) => {
+                    findings.push(
+                        Finding::new(
+                            extension_id,
+                            Severity::Error,
+                            format!("Failed to load language config: {}", err.message),
+                        )
+                        .path(config_path)
+                        .location(err.location)
+                        .repository(repository),
+                    );
+
+                    continue;
+                }
+            };
+
+            let mut message = format!("Language `{}`", config.name);
+            if let Some(grammar) = config.grammar.as_ref() {
+                message.push_str(&format!(" using grammar `{grammar}`"));
+            }
+            if !config.path_suffixes.is_empty() {
+                message.push_str(&format!(
+                    " for {}",
+                    config
+                        .path_suffixes
+                        .iter()
+                        .map(|suffix| format!("`{suffix}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            findings.push(
+                Finding::new(extension_id, Severity::Info, message)
+                    .path(config_path)
+                    .repository(repository),
+            );
+
+            language_names.push(config.name.to_string());
+        }
+
+        Ok((findings, language_names))
+    }
+}
+
+impl Survey for Languages {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("languages", "Languages");
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+
+        let mut extensions_by_language: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (extension_id, (findings, language_names)) in results {
+            report.findings.extend(findings);
+
+            for language_name in language_names {
+                let extension_ids = extensions_by_language.entry(language_name).or_default();
+                if extension_ids.last().map(String::as_str) != Some(extension_id) {
+                    extension_ids.push(extension_id.to_string());
+                }
+            }
+        }
+
+        report.groups.push(Group {
+            title: "Extensions by language".to_string(),
+            entries: extensions_by_language
+                .into_iter()
+                .map(|(language_name, extension_ids)| GroupEntry {
+                    key: language_name,
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use url::Url;
+
+use crate::extensions::ExtensionEntry;
+use crate::github;
+use crate::report::{Finding, Severity, SurveyReport};
+use crate::survey::{IssueDraft, Survey, SurveyContext};
+
+/// The length past which an extension name is considered too long to display well in the registry.
+const MAX_NAME_LENGTH: usize = 50;
+
+/// A survey of missing or weak metadata in extension manifests.
+pub struct MetadataQuality;
+
+impl MetadataQuality {
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<(Vec<Finding>, Option<IssueDraft>)> {
+        let loaded_manifest = cx.extension_index(extension_id, extension).await?.manifest;
+        let extension_manifest = &loaded_manifest.manifest;
+
+        let mut problems = Vec::new();
+
+        if extension_manifest
+            .description
+            .as_deref()
+            .is_none_or(|description| description.trim().is_empty())
+        {
+            problems.push((Severity::Warning, "Missing `description`".to_string()));
+        }
+
+        let mut has_valid_repository = false;
+        match extension_manifest.repository.as_deref() {
+            None => problems.push((Severity::Warning, "Missing `repository`".to_string())),
+            Some(repository) if repository.trim().is_empty() => {
+                problems.push((Severity::Warning, "Missing `repository`".to_string()))
+            }
+            Some(repository) => match Url::parse(repository) {
+                Ok(url) if github::is_web_url(&url) && !url.cannot_be_a_base() => {
+                    has_valid_repository = true;
+                }
+                Ok(_) => problems.push((
+                    Severity::Warning,
+                    format!("Invalid `repository` URL {repository:?}: not an HTTP(S) URL"),
+                )),
+                Err(error) => problems.push((
+                    Severity::Warning,
+                    format!("Invalid `repository` URL {repository:?}: {error}"),
+                )),
+            },
+        }
+
+        if extension_manifest.authors.is_empty() {
+            problems.push((Severity::Warning, "Empty `authors` list".to_string()));
+        }
+
+        for author in &extension_manifest.authors {
+            if !has_email(author) {
+                problems.push((
+                    Severity::Info,
+                    format!("Author {author:?} does not include an email address"),
+                ));
+            }
+        }
+
+        if let Err(error) = semver::Version::parse(&extension_manifest.version) {
+            problems.push((
+                Severity::Warning,
+                format!(
+                    "Version {:?} is not a valid semantic version: {error}",
+                    extension_manifest.version
+                ),
+            ));
+        }
+
+        let name_length = extension_manifest.name.chars().count();
+        if name_length > MAX_NAME_LENGTH {
+            problems.push((
+                Severity::Info,
+                format!(
+                    "Name {:?} is {name_length} characters long, longer than the recommended {MAX_NAME_LENGTH}",
+                    extension_manifest.name
+                ),
+            ));
+        }
+
+        if problems.is_empty() {
+            return Ok((Vec::new(), None));
+        }
+
+        let repository = extension_manifest.repository.as_deref();
+
+        // An invalid repository is already reported as a problem above, so don't draft an issue for it.
+        let issue = if let Some(repository) = repository
+            && has_valid_repository
+        {
+            let title = "Improve extension metadata".to_string();
+            let mut body = String::new();
+            body.push_str(&format!("This extension has been identified as having missing or incomplete metadata in `{}`, which is shown in the Zed extension registry.\n\n", loaded_manifest.format.file_name()));
+            body.push_str("The following problems were found:\n\n");
+            for (_, message) in &problems {
+                body.push_str(&format!("- {message}\n"));
+            }
+
+            Some(IssueDraft {
+                repository: repository.to_string(),
+                title,
+                body,
+            })
+        } else {
+            None
+        };
+
+        let manifest_path = cx.relative_path(&loaded_manifest.path);
+
+        let findings = problems
+            .into_iter()
+            .map(|(severity, message)| {
+                Finding::new(extension_id, severity, message)
+                    .path(manifest_path.clone())
+                    .repository(repository)
+            })
+            .collect();
+
+        Ok((findings, issue))
+    }
+}
+
+/// Returns whether the given author string includes an email address, as in `Jane Doe <jane@example.com>`.
+fn has_email(author: &str) -> bool {
+    let Some((_, rest)) = author.split_once('<') else {
+        return false;
+    };
+    let Some((email, _)) = rest.split_once('>') else {
+        return false;
+    };
+
+    email
+        .split_once('@')
+        .is_some_and(|(user, domain)| !user.is_empty() && !domain.is_empty())
+}
+
+impl Survey for MetadataQuality {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("metadata-quality", "Extension metadata quality");
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+        for (extension_id, (findings, issue)) in results {
+            cx.push_findings(&mut report, extension_id, findings, issue)?;
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use tokio::fs;
+
+use crate::extension::ExtensionCapability;
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Location, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext, run_blocking};
+
+/// The `zed_extension_api` helpers that run `npm` on behalf of an extension.
+const NPM_HELPERS: &[&str] = &[
+    "npm_install_package",
+    "npm_package_installed_version",
+    "npm_package_latest_version",
+];
+
+/// A process spawned from an extension's Rust sources.
+struct ProcessSpawn {
+    /// The command being spawned, or `None` if it isn't a string literal.
+    command: Option<String>,
+    path: PathBuf,
+    location: Location,
+}
+
+/// A survey comparing the `process:exec` capabilities declared by extensions with the processes
+/// their Rust sources actually spawn.
+pub struct ProcessExecUsage;
+
+impl ProcessExecUsage {
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<Vec<Finding>> {
+        let index = cx.extension_index(extension_id, extension).await?;
+        let extension_manifest = &index.manifest.manifest;
+        let repository = extension_manifest.repository.as_deref();
+        let extension_dir = extension.extension_dir(&cx.work_dir);
+
+        let mut sources = Vec::new();
+        for path in &index.files {
+            if !path.starts_with("src") || path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+
+            let source_path = extension_dir.join(path);
+            let source = fs::read_to_string(&source_path)
+                .await
+                .with_context(|| format!("failed to read {source_path:?}"))?;
+            sources.push((source_path, source));
+        }
+
+        let spawns = run_blocking(move || {
+            sources
+                .iter()
+                .flat_map(|(source_path, source)| find_process_spawns(source, source_path))
+                .collect::<Vec<_>>()
+        })
+        .await?;
+
+        let declared_commands = extension_manifest
+            .capabilities
+            .iter()
+            .filter_map(|capability| match capability {
+                ExtensionCapability::ProcessExec { command, .. } => Some(command.as_str()),
+                ExtensionCapability::Other => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut findings = Vec::new();
+
+        let mut spawns_by_command: BTreeMap<&str, &ProcessSpawn> = BTreeMap::new();
+        for spawn in &spawns {
+            match spawn.command.as_deref() {
+                Some(command) => {
+                    spawns_by_command.entry(command).or_insert(spawn);
+                }
+                None => findings.push(
+                    Finding::new(
+                        extension_id,
+                        Severity::Info,
+                        "Spawns a process with a non-literal command",
+                    )
+                    .path(cx.relative_path(&spawn.path))
+                    .location(Some(spawn.location))
+                    .repository(repository),
+                ),
+            }
+        }
+
+        for (command, spawn) in &spawns_by_command {
+            if declared_commands
+                .iter()
+                .any(|declared| declared == command || *declared == "*")
+            {
+                continue;
+            }
+
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Warning,
+                    format!(
+                        "Spawns `{command}` without declaring a `process:exec` capability for it"
+                    ),
+                )
+                .path(cx.relative_path(&spawn.path))
+                .location(Some(spawn.location))
+                .repository(repository),
+            );
+        }
+
+        let has_dynamic_spawns = spawns.iter().any(|spawn| spawn.command.is_none());
+        for command in declared_commands {
+            if command == "*" || spawns_by_command.contains_key(command) {
+                continue;
+            }
+
+            let (severity, message) = if has_dynamic_spawns {
+                (
+                    Severity::Info,
+                    format!(
+                        "Declares `process:exec` for `{command}`, which may be spawned with a non-literal command"
+                    ),
+                )
+            } else {
+                (
+                    Severity::Warning,
+                    format!("Declares `process:exec` for `{command}`, but never spawns it"),
+                )
+            };
+
+            findings.push(
+                Finding::new(extension_id, severity, message)
+                    .path(cx.relative_path(&index.manifest.path))
+                    .repository(repository),
+            );
+        }
+
+        Ok(findings)
+    }
+}
+
+impl Survey for ProcessExecUsage {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("process-exec", "Declared vs. actual process execution");
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+        report
+            .findings
+            .extend(results.into_iter().flat_map(|(_, findings)| findings));
+
+        Ok(report)
+    }
+}
+
+/// Returns the processes spawned in the given Rust source.
+///
+/// This is a lexical scan for `Command::new(...)` calls and the `npm` helpers, and not a full parse.
+fn find_process_spawns(source: &str, path: &Path) -> Vec<ProcessSpawn> {
+    let code = strip_comments(source);
+    let mut spawns = Vec::new();
+
+    for (offset, _) in code.match_indices("Command::new(") {
+        let arguments = code[offset + "Command::new(".len()..].trim_start();
+        let command = arguments
+            .strip_prefix('"')
+            .and_then(|literal| literal.split_once('"'))
+            .map(|(command, _)| command.to_string());
+
+        spawns.push(ProcessSpawn {
+            command,
+            path: path.to_path_buf(),
+            location: Location::from_offset(source, offset),
+        });
+    }
+
+    for helper in NPM_HELPERS {
+        for (offset, _) in code.match_indices(helper) {
+            let is_call = code[offset + helper.len()..].trim_start().starts_with('(');
+            let is_identifier_start = code[..offset]
+                .chars()
+                .next_back()
+                .is_none_or(|char| !char.is_alphanumeric() && char != '_');
+            if !is_call || !is_identifier_start {
+                continue;
+            }
+
+            spawns.push(ProcessSpawn {
+                command: Some("npm".to_string()),
+                path: path.to_path_buf(),
+                location: Location::from_offset(source, offset),
+            });
+        }
+    }
+
+    spawns.sort_by_key(|spawn| (spawn.location.line, spawn.location.column));
+    spawns
+}
+
+/// Replaces the comments in the given Rust source with spaces, preserving byte offsets.
+fn strip_comments(source: &str) -> String {
+    let is_identifier_char = |char: char| char.is_alphanumeric() || char == '_';
+
+    let mut code = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(char) = chars.next() {
+        if in_string {
+            code.push(char);
+            match char {
+                '\\' => code.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (char, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                code.push(char);
+            }
+            ('r', Some('"' | '#')) if !code.trim_end_matches('b').ends_with(is_identifier_char) => {
+                // Raw strings (e.g., `r#"..."#`) have no escapes and end at a `"` followed by as
+                // many `#`s as they started with, so they're copied verbatim. Anything else (e.g., a
+                // raw identifier like `r#type`) is left alone.
+                code.push(char);
+                let mut lookahead = chars.clone();
+                let mut hashes = 0;
+                while lookahead.next_if_eq(&'#').is_some() {
+                    hashes += 1;
+                }
+                if lookahead.next() != Some('"') {
+                    continue;
+                }
+
+                code.extend(chars.by_ref().take(hashes + 1));
+                while let Some(char) = chars.next() {
+                    code.push(char);
+                    let closing_hashes = chars.clone().take_while(|char| *char == '#').count();
+                    if char == '"' && closing_hashes >= hashes {
+                        code.extend(chars.by_ref().take(hashes));
+                        break;
+                    }
+                }
+            }
+            ('\'', _) => {
+                // Copy character literals verbatim so that `'"'` doesn't start a string. Anything
+                // else (e.g., a lifetime) is left alone.
+                code.push(char);
+                let mut lookahead = chars.clone();
+                let literal_len = match (lookahead.next(), lookahead.next()) {
+                    (Some('\\'), _) => lookahead.position(|char| char == '\'').map(|ix| ix + 3),
+                    (Some(_), Some('\'')) => Some(2),
+                    _ => None,
+                };
+                code.extend(chars.by_ref().take(literal_len.unwrap_or(0)));
+            }
+            ('/', Some('/')) => {
+                code.push(' ');
+                while let Some(char) = chars.next_if(|char| *char != '\n') {
+                    code.extend(std::iter::repeat_n(' ', char.len_utf8()));
+                }
+            }
+            ('/', Some('*')) => {
+                // Block comments nest in Rust.
+                chars.next();
+                code.push_str("  ");
+                let mut depth = 1;
+                while depth > 0
+                    && let Some(char) = chars.next()
+                {
+                    match (char, chars.peek()) {
+                        ('/', Some('*')) => {
+                            chars.next();
+                            depth += 1;
+                            code.push_str("  ");
+                        }
+                        ('*', Some('/')) => {
+                            chars.next();
+                            depth -= 1;
+                            code.push_str("  ");
+                        }
+                        ('\n', _) => code.push('\n'),
+                        _ => code.extend(std::iter::repeat_n(' ', char.len_utf8())),
+                    }
+                }
+            }
+            _ => code.push(char),
+        }
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(source: &str) -> Vec<Option<String>> {
+        find_process_spawns(source, Path::new("src/lib.rs"))
+            .into_iter()
+            .map(|spawn| spawn.command)
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_comments_preserves_offsets() {
+        let source = "let a = 1; // comment\n/* block\n comment */ let b = 2;";
+        let code = strip_comments(source);
+        assert_eq!(code.len(), source.len());
+        assert_eq!(
+            code,
+            "let a = 1;           \n        \n            let b = 2;"
+        );
+    }
+
+    #[test]
+    fn test_strip_comments_nested_block_comments() {
+        let code = strip_comments("/* a /* b */ c */x/*/ d */y");
+        assert_eq!(code, "                 x        y");
+    }
+
+    #[test]
+    fn test_strip_comments_keeps_strings() {
+        assert_eq!(
+            strip_comments(r#"let s = "// not a comment";"#),
+            r#"let s = "// not a comment";"#
+        );
+        assert_eq!(
+            strip_comments(r#"let s = "\" // still a string";"#),
+            r#"let s = "\" // still a string";"#
+        );
+    }
+
+    #[test]
+    fn test_strip_comments_char_literals_and_lifetimes() {
+        assert_eq!(strip_comments("let c = '\"'; // x"), "let c = '\"';     ");
+        assert_eq!(strip_comments("let c = '\\''; // x"), "let c = '\\'';     ");
+        assert_eq!(
+            strip_comments("fn f<'a>(s: &'a str) {} // x"),
+            "fn f<'a>(s: &'a str) {}     "
+        );
+    }
+
+    #[test]
+    fn test_strip_comments_raw_strings() {
+        assert_eq!(
+            strip_comments(r##"let s = r#"a " // b"#; // c"##),
+            r##"let s = r#"a " // b"#;     "##
+        );
+        assert_eq!(
+            strip_comments(r#"let s = r"\"; // c"#),
+            r#"let s = r"\";     "#
+        );
+        assert_eq!(
+            strip_comments(r#"let s = br"x"; // c"#),
+            r#"let s = br"x";     "#
+        );
+        assert_eq!(
+            strip_comments("let r#type = 1; // c"),
+            "let r#type = 1;     "
+        );
+        assert_eq!(
+            strip_comments("let bar = \"\"; // c"),
+            "let bar = \"\";     "
+        );
+    }
+
+    #[test]
+    fn test_find_process_spawns() {
+        assert_eq!(
+            commands(r#"Command::new("git"); zed::Command::new(binary);"#),
+            vec![Some("git".to_string()), None]
+        );
+        assert_eq!(
+            commands(r#"// Command::new("git")"#),
+            Vec::<Option<String>>::new()
+        );
+        assert_eq!(
+            commands(r##"let c = '"'; let s = r#"say "hi""#; Command::new("sh");"##),
+            vec![Some("sh".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_find_process_spawns_npm_helpers() {
+        assert_eq!(
+            commands(r#"zed::npm_install_package("pkg", &version)?;"#),
+            vec![Some("npm".to_string())]
+        );
+        assert_eq!(
+            commands("let v = npm_package_latest_version (\"pkg\")?;"),
+            vec![Some("npm".to_string())]
+        );
+        assert_eq!(
+            commands("my_npm_install_package(); use zed::npm_install_package;"),
+            Vec::<Option<String>>::new()
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the slash commands, context servers, and indexed docs providers provided by extensions.
+pub struct ProviderInventory;
+
+impl Survey for ProviderInventory {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new(
+            "providers",
+            "Slash commands, context servers, and indexed docs providers",
+        );
+
+        let manifests = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok(index.manifest)
+            })
+            .await?;
+
+        let mut extensions_by_slash_command: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        for (extension_id, loaded_manifest) in &manifests {
+            for command_name in loaded_manifest.manifest.slash_commands.keys() {
+                extensions_by_slash_command
+                    .entry(command_name)
+                    .or_default()
+                    .push(extension_id.to_string());
+            }
+        }
+
+        let mut extensions_by_indexed_docs_provider: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+        for (extension_id, loaded_manifest) in &manifests {
+            let extension_manifest = &loaded_manifest.manifest;
+            let manifest_path = cx.relative_path(&loaded_manifest.path);
+            let repository = extension_manifest.repository.as_deref();
+            let finding = |severity, message| {
+                Finding::new(*extension_id, severity, message)
+                    .path(manifest_path.clone())
+                    .repository(repository)
+            };
+
+            for (command_name, command) in &extension_manifest.slash_commands {
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!("Provides slash command `/{command_name}`"),
+                ));
+
+                if command.description.trim().is_empty() {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!("Slash command `/{command_name}` is missing a description"),
+                    ));
+                }
+
+                let other_extensions = extensions_by_slash_command[command_name.as_ref()]
+                    .iter()
+                    .filter(|other| other != extension_id)
+                    .map(|other| format!("`{other}`"))
+                    .collect::<Vec<_>>();
+                if !other_extensions.is_empty() {
+                    report.findings.push(finding(
+                        Severity::Warning,
+                        format!(
+                            "Slash command `/{command_name}` is also provided by {}",
+                            other_extensions.join(", ")
+                        ),
+                    ));
+                }
+            }
+
+            for server_name in extension_manifest.context_servers.keys() {
+                report.findings.push(finding(
+                    Severity::Info,
+                    format!("Provides context server `{server_name}`"),
+                ));
+            }
+
+            if extension_manifest.indexed_docs_providers.is_empty() {
+                continue;
+            }
+
+            let github_issue_url = if let Some(repository) = repository {
+                let title = "Indexed docs providers are being deprecated";
+                let mut body = String::new();
+                body.push_str("This extension has been identified as providing indexed docs providers, which are being deprecated:\n\n");
+                for provider_name in extension_manifest.indexed_docs_providers.keys() {
+                    body.push_str(&format!("- `{provider_name}`\n"));
+                }
+                body.push_str("\nPlease remove the `indexed_docs_providers` from `extension.toml` (and the corresponding implementation) before support is dropped from Zed.");
+
+                cx.issue_url(&mut report, extension_id, repository, title, &body)?
+            } else {
+                None
+            };
+
+            for provider_name in extension_manifest.indexed_docs_providers.keys() {
+                report.findings.push(
+                    finding(
+                        Severity::Warning,
+                        format!("Provides indexed docs provider `{provider_name}`, which is being deprecated"),
+                    )
+                    .issue_url(github_issue_url.clone()),
+                );
+
+                extensions_by_indexed_docs_provider
+                    .entry(provider_name)
+                    .or_default()
+                    .push(extension_id.to_string());
+            }
+        }
+
+        report.groups.push(Group {
+            title: "Slash commands provided by multiple extensions".to_string(),
+            entries: extensions_by_slash_command
+                .into_iter()
+                .filter(|(_, extension_ids)| extension_ids.len() > 1)
+                .map(|(command_name, extension_ids)| GroupEntry {
+                    key: format!("`/{command_name}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+        report.groups.push(Group {
+            title: "Indexed docs providers".to_string(),
+            entries: extensions_by_indexed_docs_provider
+                .into_iter()
+                .map(|(provider_name, extension_ids)| GroupEntry {
+                    key: format!("`{provider_name}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
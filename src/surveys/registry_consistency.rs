@@ -0,0 +1,132 @@
+use std::path::{Component, Path};
+
+use anyhow::Result;
+use tokio::fs;
+
+use crate::extension::ManifestFormat;
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey checking that each extension's manifest agrees with its entry in `extensions.toml`.
+pub struct RegistryConsistency;
+
+impl RegistryConsistency {
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<Vec<Finding>> {
+        if let Some(path) = extension.path.as_ref()
+            && let Some(problem) = self.check_path(cx, extension, path).await
+        {
+            return Ok(vec![
+                Finding::new(
+                    extension_id,
+                    Severity::Error,
+                    format!("`path` {path:?} in `extensions.toml` {problem}"),
+                )
+                .path("extensions.toml"),
+            ]);
+        }
+
+        let index = cx.extension_index(extension_id, extension).await?;
+        let extension_manifest = &index.manifest.manifest;
+        let manifest_path = cx.relative_path(&index.manifest.path);
+        let repository = extension_manifest.repository.as_deref();
+
+        let mut findings = Vec::new();
+
+        if extension_manifest.version.as_ref() != extension.version {
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Error,
+                    format!(
+                        "Manifest version {} does not match version {} in `extensions.toml`",
+                        extension_manifest.version, extension.version
+                    ),
+                )
+                .path(manifest_path.clone())
+                .repository(repository),
+            );
+        }
+
+        // Legacy `extension.json` manifests don't have an ID, so there is nothing to compare.
+        if index.manifest.format == ManifestFormat::Toml
+            && extension_manifest.id.as_ref() != extension_id
+        {
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Error,
+                    format!(
+                        "Manifest ID `{}` does not match ID `{extension_id}` in `extensions.toml`",
+                        extension_manifest.id
+                    ),
+                )
+                .path(manifest_path)
+                .repository(repository),
+            );
+        }
+
+        Ok(findings)
+    }
+
+    /// Checks that the `path` of the given extension points to a directory inside its submodule,
+    /// returning a description of the problem if it doesn't.
+    async fn check_path(
+        &self,
+        cx: &SurveyContext,
+        extension: &ExtensionEntry,
+        path: &str,
+    ) -> Option<&'static str> {
+        let escapes_submodule = Path::new(path).components().any(|component| {
+            matches!(
+                component,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if escapes_submodule {
+            return Some("points outside of the submodule");
+        }
+
+        let submodule_dir = cx.work_dir.join(&extension.submodule);
+        let (Ok(submodule_dir), Ok(extension_dir)) = (
+            fs::canonicalize(&submodule_dir).await,
+            fs::canonicalize(extension.extension_dir(&cx.work_dir)).await,
+        ) else {
+            return Some("does not exist");
+        };
+
+        if !extension_dir.starts_with(&submodule_dir) {
+            return Some("points outside of the submodule");
+        }
+        if !extension_dir.is_dir() {
+            return Some("is not a directory");
+        }
+
+        None
+    }
+}
+
+impl Survey for RegistryConsistency {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new(
+            "registry-consistency",
+            "Manifest and `extensions.toml` consistency",
+        );
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+        report
+            .findings
+            .extend(results.into_iter().flat_map(|(_, findings)| findings));
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use crate::extension::{ManifestFormat, SchemaVersion};
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of the manifest schema versions in use across extensions.
+pub struct SchemaVersions {
+    min_schema_version: SchemaVersion,
+}
+
+impl SchemaVersions {
+    pub fn new(min_schema_version: i32) -> Self {
+        Self {
+            min_schema_version: SchemaVersion(min_schema_version),
+        }
+    }
+}
+
+impl Survey for SchemaVersions {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("schema-versions", "Manifest schema versions");
+
+        let manifests = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok(index.manifest)
+            })
+            .await?;
+
+        let mut extensions_by_schema_version: BTreeMap<SchemaVersion, Vec<String>> =
+            BTreeMap::new();
+
+        for (extension_id, loaded_manifest) in manifests {
+            let extension_manifest = loaded_manifest.manifest;
+            let schema_version = extension_manifest.schema_version;
+
+            extensions_by_schema_version
+                .entry(schema_version)
+                .or_default()
+                .push(extension_id.to_string());
+
+            if schema_version >= self.min_schema_version {
+                continue;
+            }
+
+            let github_issue_url = if let Some(repository) = extension_manifest.repository.as_ref()
+            {
+                const ZED_DOCS_URL: &str = "https://zed.dev/docs/extensions/developing-extensions";
+
+                let title = format!(
+                    "Migrate to manifest schema version {}",
+                    self.min_schema_version.0
+                );
+                let mut body = String::new();
+                body.push_str(&format!(
+                    "This extension has been identified as using manifest schema version {}, and support for schema versions below {} is planned to be dropped.\n\n",
+                    schema_version.0, self.min_schema_version.0
+                ));
+                if loaded_manifest.format == ManifestFormat::Json {
+                    body.push_str("This extension still uses the legacy `extension.json` manifest format, which should be migrated to `extension.toml`.\n\n");
+                }
+                body.push_str(&format!(
+                    "See the [Zed extension documentation]({ZED_DOCS_URL}) for more information."
+                ));
+
+                cx.issue_url(&mut report, extension_id, repository, &title, &body)?
+            } else {
+                None
+            };
+
+            report.findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Warning,
+                    format!(
+                        "Uses manifest schema version {}, which is below the minimum of {}",
+                        schema_version.0, self.min_schema_version.0
+                    ),
+                )
+                .path(cx.relative_path(&loaded_manifest.path))
+                .repository(extension_manifest.repository.as_deref())
+                .issue_url(github_issue_url),
+            );
+        }
+
+        report.groups.push(Group {
+            title: "Extensions by schema version".to_string(),
+            entries: extensions_by_schema_version
+                .into_iter()
+                .map(|(schema_version, extension_ids)| GroupEntry {
+                    key: format!("Schema version {}", schema_version.0),
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
@@ -1,14 +1,9 @@
-use std::io::Write;
-use std::path::Path;
-
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use tokio::fs;
-use tokio::io::AsyncReadExt;
 
-use crate::extension::ExtensionManifest;
-use crate::extensions::{ExtensionsToml, ThemeFamily};
-use crate::github;
-use crate::survey::Survey;
+use crate::extensions::ExtensionEntry;
+use crate::report::{Finding, Location, Severity, SurveyReport};
+use crate::survey::{IssueDraft, Survey, SurveyContext};
 
 pub struct ThemePropertyUsage {
     theme_property: Vec<String>,
@@ -27,145 +22,142 @@ impl ThemePropertyUsage {
             .collect::<Vec<String>>()
             .join(" in section ")
     }
-}
 
-impl Survey for ThemePropertyUsage {
-    async fn run(
-        &self,
-        work_dir: impl AsRef<Path>,
-        extensions_toml: &ExtensionsToml,
-    ) -> Result<()> {
-        let work_dir = work_dir.as_ref();
-        let mut report = Vec::new();
+    /// Returns the best-effort [`Location`] of the surveyed property within the given theme file.
+    ///
+    /// This looks for the theme's name and then for each key of the property, in order.
+    fn locate_property(&self, theme_file: &str, theme_name: &str) -> Option<Location> {
+        let theme_name = serde_json::to_string(theme_name).ok()?;
+        let mut offset = theme_file.find(&theme_name)?;
+        for key in &self.theme_property {
+            let key = serde_json::to_string(key).ok()?;
+            offset += theme_file[offset..].find(&key)?;
+        }
 
-        let property_iterator = self.theme_property.iter();
+        Some(Location::from_offset(theme_file, offset))
+    }
 
-        for (extension_id, extension) in &extensions_toml.extensions {
-            let mut themes_dir = extension.extension_dir(work_dir);
-            themes_dir.push("themes");
+    async fn survey_extension(
+        &self,
+        cx: &SurveyContext,
+        extension_id: &str,
+        extension: &ExtensionEntry,
+    ) -> Result<(Vec<Finding>, Option<IssueDraft>)> {
+        let mut findings = Vec::new();
+        let property_iterator = self.theme_property.iter();
 
-            if !fs::try_exists(&themes_dir).await? {
-                continue;
-            }
+        let index = cx.extension_index(extension_id, extension).await?;
+        let repository = index.manifest.manifest.repository.as_deref();
+        let extension_dir = extension.extension_dir(&cx.work_dir);
+
+        let mut themes_using_property = Vec::new();
+
+        for theme_file in &index.themes {
+            let theme_path = extension_dir.join(&theme_file.path);
+            let theme_family = match &theme_file.contents {
+                Ok(theme_family) => theme_family,
+                Err(err) => {
+                    findings.push(
+                        Finding::new(
+                            extension_id,
+                            Severity::Error,
+                            format!("Failed to load theme file: {}", err.message),
+                        )
+                        .path(cx.relative_path(&theme_path))
+                        .location(err.location)
+                        .repository(repository),
+                    );
 
-            let extension_manifest: ExtensionManifest = {
-                let extension_manifest_path =
-                    extension.extension_dir(work_dir).join("extension.toml");
-                if extension_manifest_path.exists() {
-                    toml::from_str(&fs::read_to_string(&extension_manifest_path).await?)?
-                } else {
-                    let extension_manifest_json_path =
-                        extension.extension_dir(work_dir).join("extension.json");
-                    serde_json_lenient::from_str(
-                        &fs::read_to_string(&extension_manifest_json_path).await?,
-                    )?
+                    continue;
                 }
             };
 
-            fn write_extension_header(
-                report: &mut Vec<u8>,
-                extension_id: &str,
-                extension_manifest: &ExtensionManifest,
-            ) -> Result<()> {
-                writeln!(report, "- [ ] `{extension_id}`")?;
-                write!(report, "  - Repository: ")?;
-                if let Some(repository) = extension_manifest.repository.as_ref() {
-                    writeln!(report, "[{repository}]({repository})")?;
-                } else {
-                    writeln!(report, "???")?;
+            let mut theme_file_contents = None;
+            for theme in &theme_family.themes {
+                let uses_property = property_iterator
+                    .clone()
+                    .try_fold(&theme.style, |style, key| {
+                        style.as_object().and_then(|obj| obj.get(key))
+                    })
+                    .is_some();
+                if uses_property {
+                    if theme_file_contents.is_none() {
+                        theme_file_contents = Some(
+                            fs::read_to_string(&theme_path)
+                                .await
+                                .with_context(|| format!("failed to read {theme_path:?}"))?,
+                        );
+                    }
+                    let location = theme_file_contents
+                        .as_deref()
+                        .and_then(|contents| self.locate_property(contents, &theme.name));
+                    themes_using_property.push((theme_path.clone(), theme, location));
                 }
-
-                writeln!(report, "  - Issue: TBD")?;
-
-                Ok(())
             }
+        }
 
-            let mut themes = Vec::new();
-
-            let mut themes_entries = fs::read_dir(&themes_dir).await?;
-            while let Some(entry) = themes_entries.next_entry().await? {
-                let theme_path = entry.path();
-                if theme_path
-                    .extension()
-                    .is_none_or(|extension| extension != "json")
-                {
-                    continue;
-                }
+        if themes_using_property.is_empty() {
+            return Ok((findings, None));
+        }
 
-                let mut buf = String::new();
-                fs::File::open(&theme_path)
-                    .await?
-                    .read_to_string(&mut buf)
-                    .await?;
-                let theme = match serde_json_lenient::from_str_lenient::<ThemeFamily>(&buf) {
-                    Ok(theme) => theme,
-                    Err(err) => {
-                        write_extension_header(&mut report, extension_id, &extension_manifest)?;
-                        writeln!(report, "  - Errors:")?;
-                        writeln!(
-                            &mut report,
-                            "    - Failed to parse theme file at {theme_path:?}: {err}"
-                        )?;
-
-                        continue;
-                    }
-                };
+        let issue = repository.map(|repository| {
+            let title = format!("Deprecated `{}` usage", self.property_name());
+            let mut issue_body = String::new();
+            issue_body.push_str("This extension has been identified as using the deprecated `scrollbar_thumb.background` style property.\n\n");
+            issue_body.push_str("This property has been deprecated in favor of `scrollbar.thumb.background`. Please migrate to using the new property.\n\n");
+            issue_body.push_str("The following themes are impacted:\n\n");
 
-                themes.extend(theme.themes);
+            for (_, theme, _) in &themes_using_property {
+                issue_body.push_str(&format!(
+                    "- Theme {:?} is using deprecated style property {}\n",
+                    theme.name,
+                    self.property_name()
+                ));
             }
 
-            let themes_using_property = themes
-                .into_iter()
-                .filter(|theme| {
-                    property_iterator
-                        .clone()
-                        .fold(Some(&theme.style), |style, key| {
-                            style.and_then(|s| s.as_object().and_then(|obj| obj.get(key)))
-                        })
-                        .is_some()
-                })
-                .collect::<Vec<_>>();
-            if themes_using_property.is_empty() {
-                continue;
+            IssueDraft {
+                repository: repository.to_string(),
+                title,
+                body: issue_body,
             }
-
-            write_extension_header(&mut report, extension_id, &extension_manifest)?;
-
-            if let Some(repository) = &extension_manifest.repository {
-                let title = format!("Deprecated `{}` usage", self.property_name());
-                let mut issue_body = String::new();
-                issue_body.push_str("This extension has been identified as using the deprecated `scrollbar_thumb.background` style property.\n\n");
-                issue_body.push_str("This property has been deprecated in favor of `scrollbar.thumb.background`. Please migrate to using the new property.\n\n");
-                issue_body.push_str("The following themes are impacted:\n\n");
-
-                for theme in &themes_using_property {
-                    issue_body.push_str(&format!(
-                        "- Theme {:?} is using deprecated style property {}\n",
+        });
+
+        for (theme_path, theme, location) in &themes_using_property {
+            findings.push(
+                Finding::new(
+                    extension_id,
+                    Severity::Warning,
+                    format!(
+                        "Theme {:?} is using deprecated style property {}",
                         theme.name,
                         self.property_name()
-                    ));
-                }
-
-                let github_issue_url =
-                    github::create_github_issue_url(repository, &title, &issue_body)?;
-
-                writeln!(&mut report, "    - [Create Issue]({github_issue_url})")?;
-            }
-
-            writeln!(&mut report, "  - Errors:")?;
-
-            for theme in &themes_using_property {
-                writeln!(
-                    &mut report,
-                    "    - Theme {:?} is using deprecated style property {}",
-                    theme.name,
-                    self.property_name()
-                )?;
-            }
+                    ),
+                )
+                .path(cx.relative_path(theme_path))
+                .location(*location)
+                .repository(repository),
+            );
         }
+        Ok((findings, issue))
+    }
+}
 
-        println!("{}", String::from_utf8_lossy(&report));
+impl Survey for ThemePropertyUsage {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new(
+            "theme-property",
+            format!("Extensions using {}", self.property_name()),
+        );
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| {
+                self.survey_extension(cx, extension_id, extension)
+            })
+            .await?;
+        for (extension_id, (findings, issue)) in results {
+            cx.push_findings(&mut report, extension_id, findings, issue)?;
+        }
 
-        Ok(())
+        Ok(report)
     }
 }
@@ -1,43 +1,87 @@
-use std::collections::HashMap;
-use std::io::Write;
+use std::collections::{BTreeMap, BTreeSet};
 
-use tokio::fs;
+use anyhow::Result;
 
-use crate::extension::ExtensionManifest;
-use crate::survey::Survey;
+use crate::language::BUILTIN_GRAMMARS;
+use crate::report::{Finding, Group, GroupEntry, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
 
 pub struct TreeSitterGrammars;
 
 impl Survey for TreeSitterGrammars {
-    async fn run(
-        &self,
-        work_dir: impl AsRef<std::path::Path>,
-        extensions_toml: &crate::extensions::ExtensionsToml,
-    ) -> anyhow::Result<()> {
-        let work_dir = work_dir.as_ref();
-        let mut report = Vec::new();
-
-        let mut extensions_by_grammar: HashMap<String, Vec<String>> = HashMap::new();
-
-        for (extension_id, extension) in &extensions_toml.extensions {
-            let extension_toml_path = extension.extension_dir(work_dir).join("extension.toml");
-            if !extension_toml_path.exists() {
-                continue;
-            }
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("tree-sitter-grammars", "Tree-sitter grammars");
+
+        let mut extensions_by_grammar: BTreeMap<String, Vec<String>> = BTreeMap::new();
 
-            let extension_manifest: ExtensionManifest =
-                toml::from_str(&fs::read_to_string(&extension_toml_path).await?)?;
+        let indexes = cx
+            .survey_extensions(&mut report, |extension_id, extension| async {
+                let index = cx.extension_index(extension_id, extension).await?;
+                Ok((index, extension.extension_dir(&cx.work_dir)))
+            })
+            .await?;
 
-            if !extension_manifest.grammars.is_empty() {
-                writeln!(report, "- {extension_id}")?;
+        let mut extensions_by_grammar_name: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut extensions_by_unused_grammar: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let mut extensions_by_missing_grammar: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let mut used_grammar_names = BTreeSet::new();
+        for (extension_id, (index, _)) in &indexes {
+            for grammar_name in index.manifest.manifest.grammars.keys() {
+                extensions_by_grammar_name
+                    .entry(grammar_name)
+                    .or_default()
+                    .push(extension_id);
+            }
+            for language in &index.languages {
+                if let Ok(config) = &language.contents
+                    && let Some(grammar_name) = config.grammar.as_deref()
+                {
+                    used_grammar_names.insert(grammar_name);
+                }
             }
+        }
 
-            for (grammar_name, grammar) in extension_manifest.grammars {
-                writeln!(report, "  - {grammar_name}")?;
-                writeln!(report, "    - Repo: {}", grammar.repository)?;
-                writeln!(report, "    - Rev: {}", grammar.rev)?;
+        for (extension_id, (index, extension_dir)) in &indexes {
+            let extension_manifest = &index.manifest.manifest;
+            let repository = extension_manifest.repository.as_deref();
+
+            for (grammar_name, grammar) in &extension_manifest.grammars {
+                // Grammars declared in the legacy `extension.json` format have no repository.
+                let mut message = if grammar.repository.is_empty() {
+                    format!("Grammar `{grammar_name}`")
+                } else {
+                    format!(
+                        "Grammar `{grammar_name}` from {} at {}",
+                        grammar.repository, grammar.rev
+                    )
+                };
                 if let Some(path) = grammar.path.as_ref() {
-                    writeln!(report, "    - Path: {}", path)?;
+                    message.push_str(&format!(" (path: {path})"));
+                }
+                report.findings.push(
+                    Finding::new(*extension_id, Severity::Info, message).repository(repository),
+                );
+
+                if !used_grammar_names.contains(grammar_name.as_ref()) {
+                    extensions_by_unused_grammar
+                        .entry(grammar_name)
+                        .or_default()
+                        .push(extension_id.to_string());
+                    report.findings.push(
+                        Finding::new(
+                            *extension_id,
+                            Severity::Warning,
+                            format!(
+                                "Grammar `{grammar_name}` is declared but not used by any language"
+                            ),
+                        )
+                        .path(cx.relative_path(&index.manifest.path))
+                        .repository(repository),
+                    );
+                }
+
+                if grammar.repository.is_empty() {
+                    continue;
                 }
 
                 let full_grammar_path = format!(
@@ -45,33 +89,98 @@ impl Survey for TreeSitterGrammars {
                     grammar.repository.trim_end_matches(".git"),
                     grammar
                         .path
+                        .as_ref()
                         .map(|path| format!("/{path}"))
                         .unwrap_or_default()
                 );
                 extensions_by_grammar
                     .entry(full_grammar_path)
                     .or_default()
-                    .push(extension_id.clone());
+                    .push(extension_id.to_string());
             }
-        }
 
-        println!("{}", String::from_utf8_lossy(&report));
+            for language in &index.languages {
+                let Ok(config) = &language.contents else {
+                    continue;
+                };
+                let Some(grammar_name) = config.grammar.as_deref() else {
+                    continue;
+                };
+                if extension_manifest.grammars.contains_key(grammar_name)
+                    || BUILTIN_GRAMMARS.contains(&grammar_name)
+                {
+                    continue;
+                }
 
-        let dupes = extensions_by_grammar
-            .iter()
-            .filter(|(_, extensions)| extensions.len() > 1)
-            .collect::<Vec<_>>();
+                let finding = |severity, message| {
+                    Finding::new(*extension_id, severity, message)
+                        .path(cx.relative_path(&extension_dir.join(&language.path)))
+                        .repository(repository)
+                };
+                match extensions_by_grammar_name.get(grammar_name) {
+                    Some(providers) => report.findings.push(finding(
+                        Severity::Info,
+                        format!(
+                            "Language `{}` uses grammar `{grammar_name}`, which is only provided by {}",
+                            config.name,
+                            providers
+                                .iter()
+                                .map(|provider| format!("`{provider}`"))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    )),
+                    None => {
+                        report.findings.push(finding(
+                            Severity::Warning,
+                            format!(
+                                "Language `{}` uses grammar `{grammar_name}`, which is not provided by any extension or built into Zed",
+                                config.name
+                            ),
+                        ));
 
-        if !dupes.is_empty() {
-            println!("Grammars provided by multiple extensions:");
-            for (grammar, extensions) in dupes {
-                println!("Grammar: {}", grammar);
-                for extension in extensions {
-                    println!("  - {}", extension);
+                        let extension_ids =
+                            extensions_by_missing_grammar.entry(grammar_name).or_default();
+                        if extension_ids.last().map(String::as_str) != Some(extension_id) {
+                            extension_ids.push(extension_id.to_string());
+                        }
+                    }
                 }
             }
         }
 
-        Ok(())
+        report.groups.push(Group {
+            title: "Grammars provided by multiple extensions".to_string(),
+            entries: extensions_by_grammar
+                .into_iter()
+                .filter(|(_, extensions)| extensions.len() > 1)
+                .map(|(grammar, extension_ids)| GroupEntry {
+                    key: grammar,
+                    extension_ids,
+                })
+                .collect(),
+        });
+        report.groups.push(Group {
+            title: "Grammars used by languages but not provided by any extension".to_string(),
+            entries: extensions_by_missing_grammar
+                .into_iter()
+                .map(|(grammar_name, extension_ids)| GroupEntry {
+                    key: format!("`{grammar_name}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+        report.groups.push(Group {
+            title: "Grammars not used by any language".to_string(),
+            entries: extensions_by_unused_grammar
+                .into_iter()
+                .map(|(grammar_name, extension_ids)| GroupEntry {
+                    key: format!("`{grammar_name}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
     }
 }
@@ -0,0 +1,537 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{Context as _, Result};
+use serde_json::Value;
+use tokio::fs;
+
+use crate::extension::{LoadedManifest, ManifestFormat};
+use crate::manifest_schema::{self, Schema};
+use crate::report::{Finding, Group, GroupEntry, Location, Severity, SurveyReport};
+use crate::survey::{Survey, SurveyContext};
+
+/// A survey of manifest keys that Zed does not read, such as misspelled section names.
+pub struct UnknownManifestKeys;
+
+/// A segment of the path to a value within a manifest.
+#[derive(Clone)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// A key found in a manifest that is not part of its schema.
+struct UnknownKey {
+    path: Vec<PathSegment>,
+    suggestion: Option<&'static str>,
+}
+
+impl UnknownKey {
+    fn key(&self) -> &str {
+        match self.path.last() {
+            Some(PathSegment::Key(key)) => key,
+            Some(PathSegment::Index(_)) | None => "",
+        }
+    }
+
+    fn description(&self) -> String {
+        match self.suggestion {
+            Some(suggestion) => format!("Unknown key `{self}` (did you mean `{suggestion}`?)"),
+            None => format!("Unknown key `{self}`"),
+        }
+    }
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.path.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) => {
+                    if index > 0 {
+                        write!(f, ".")?;
+                    }
+                    if !key.is_empty()
+                        && key
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                    {
+                        write!(f, "{key}")?;
+                    } else {
+                        write!(f, "{key:?}")?;
+                    }
+                }
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl UnknownManifestKeys {
+    async fn survey_manifest(
+        &self,
+        loaded_manifest: &LoadedManifest,
+    ) -> Result<Vec<(UnknownKey, Option<Location>)>> {
+        let manifest_path = &loaded_manifest.path;
+        let contents = fs::read_to_string(manifest_path)
+            .await
+            .with_context(|| format!("failed to read {manifest_path:?}"))?;
+
+        let (value, schema): (Value, _) = match loaded_manifest.format {
+            ManifestFormat::Toml => (
+                toml::from_str(&contents)
+                    .with_context(|| format!("failed to parse {manifest_path:?}"))?,
+                &manifest_schema::EXTENSION_TOML,
+            ),
+            ManifestFormat::Json => (
+                serde_json_lenient::from_str(&contents)
+                    .with_context(|| format!("failed to parse {manifest_path:?}"))?,
+                &manifest_schema::EXTENSION_JSON,
+            ),
+        };
+
+        let mut unknown_keys = Vec::new();
+        find_unknown_keys(schema, &value, &mut Vec::new(), &mut unknown_keys);
+
+        Ok(unknown_keys
+            .into_iter()
+            .map(|unknown_key| {
+                let location = locate_key(&contents, loaded_manifest.format, &unknown_key.path);
+                (unknown_key, location)
+            })
+            .collect())
+    }
+}
+
+/// Collects the keys in `value` that are not part of `schema`, recursing into nested values.
+fn find_unknown_keys(
+    schema: &'static Schema,
+    value: &Value,
+    path: &mut Vec<PathSegment>,
+    unknown_keys: &mut Vec<UnknownKey>,
+) {
+    match (schema, value) {
+        (Schema::Table(_) | Schema::Map(_), Value::Object(object)) => {
+            for (key, value) in object {
+                path.push(PathSegment::Key(key.clone()));
+                if let Some(schema) = schema.key(key) {
+                    find_unknown_keys(schema, value, path, unknown_keys);
+                } else {
+                    unknown_keys.push(UnknownKey {
+                        path: path.clone(),
+                        suggestion: suggest_key(schema, key),
+                    });
+                }
+                path.pop();
+            }
+        }
+        (Schema::Array(schema), Value::Array(array)) => {
+            for (index, value) in array.iter().enumerate() {
+                path.push(PathSegment::Index(index));
+                find_unknown_keys(schema, value, path, unknown_keys);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the known key closest to `key`, if any is close enough to be a likely misspelling.
+fn suggest_key(schema: &Schema, key: &str) -> Option<&'static str> {
+    let max_distance = (key.chars().count() / 3).max(1);
+
+    schema
+        .known_keys()
+        .map(|known_key| (edit_distance(key, known_key), known_key))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known_key)| known_key)
+}
+
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    let mut current_row = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current_row[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    previous_row[b.len()]
+}
+
+/// Returns the best-effort [`Location`] of the key at the given path within the manifest.
+///
+/// This looks for each key of the path, in order, skipping array indices.
+fn locate_key(contents: &str, format: ManifestFormat, path: &[PathSegment]) -> Option<Location> {
+    let mut offset = 0;
+    for segment in path {
+        let PathSegment::Key(key) = segment else {
+            continue;
+        };
+
+        offset += match format {
+            ManifestFormat::Json => {
+                let key = serde_json::to_string(key).ok()?;
+                contents[offset..].find(&key)?
+            }
+            ManifestFormat::Toml => find_bare_key(&contents[offset..], key)?,
+        };
+    }
+
+    Some(Location::from_offset(contents, offset))
+}
+
+/// Returns the offset of the first occurrence of `key` in `text` that is not part of a longer key.
+fn find_bare_key(text: &str, key: &str) -> Option<usize> {
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+
+    text.match_indices(key)
+        .find(|(offset, _)| {
+            let preceding = text[..*offset].chars().next_back();
+            let following = text[offset + key.len()..].chars().next();
+            !preceding.is_some_and(is_key_char) && !following.is_some_and(is_key_char)
+        })
+        .map(|(offset, _)| offset)
+}
+
+impl Survey for UnknownManifestKeys {
+    async fn run(&self, cx: &SurveyContext) -> Result<SurveyReport> {
+        let mut report = SurveyReport::new("unknown-manifest-keys", "Unknown manifest keys");
+
+        let results = cx
+            .survey_extensions(&mut report, |extension_id, extension| async move {
+                let loaded_manifest = cx.extension_index(extension_id, extension).await?.manifest;
+                let unknown_keys = self.survey_manifest(&loaded_manifest).await?;
+                Ok((loaded_manifest, unknown_keys))
+            })
+            .await?;
+
+        let mut extensions_by_unknown_key: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for (extension_id, (loaded_manifest, unknown_keys)) in results {
+            if unknown_keys.is_empty() {
+                continue;
+            }
+
+            let repository = loaded_manifest.manifest.repository.as_deref();
+            let file_name = loaded_manifest.format.file_name();
+
+            let github_issue_url = if let Some(repository) = repository {
+                let title = format!("Unknown keys in `{file_name}`");
+                let mut body = String::new();
+                body.push_str(&format!("This extension has been identified as having keys in `{file_name}` that Zed does not read. These are often misspellings of known keys, and the sections they contain are silently ignored.\n\n"));
+                body.push_str("The following keys are impacted:\n\n");
+                for (unknown_key, _) in &unknown_keys {
+                    body.push_str(&format!("- {}\n", unknown_key.description()));
+                }
+
+                cx.issue_url(&mut report, extension_id, repository, &title, &body)?
+            } else {
+                None
+            };
+
+            let manifest_path = cx.relative_path(&loaded_manifest.path);
+
+            for (unknown_key, location) in &unknown_keys {
+                let extension_ids = extensions_by_unknown_key
+                    .entry(unknown_key.key().to_string())
+                    .or_default();
+                if extension_ids.last().map(String::as_str) != Some(extension_id) {
+                    extension_ids.push(extension_id.to_string());
+                }
+
+                report.findings.push(
+                    Finding::new(extension_id, Severity::Warning, unknown_key.description())
+                        .path(manifest_path.clone())
+                        .location(*location)
+                        .repository(repository)
+                        .issue_url(github_issue_url.clone()),
+                );
+            }
+        }
+
+        report.groups.push(Group {
+            title: "Extensions by unknown key".to_string(),
+            entries: extensions_by_unknown_key
+                .into_iter()
+                .map(|(key, extension_ids)| GroupEntry {
+                    key: format!("`{key}`"),
+                    extension_ids,
+                })
+                .collect(),
+        });
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::extension::ExtensionManifest;
+
+    fn unknown_keys(schema: &'static Schema, value: Value) -> Vec<(String, Option<&'static str>)> {
+        let mut unknown_keys = Vec::new();
+        find_unknown_keys(schema, &value, &mut Vec::new(), &mut unknown_keys);
+        unknown_keys
+            .into_iter()
+            .map(|unknown_key| (unknown_key.to_string(), unknown_key.suggestion))
+            .collect()
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("grammars", "grammars"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("grammer", "grammars"), 2);
+        assert_eq!(edit_distance("langauge_servers", "language_servers"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("thème", "theme"), 1);
+    }
+
+    #[test]
+    fn test_suggest_key() {
+        let schema = &manifest_schema::EXTENSION_TOML;
+        assert_eq!(suggest_key(schema, "grammer"), Some("grammars"));
+        assert_eq!(
+            suggest_key(schema, "langauge_servers"),
+            Some("language_servers")
+        );
+        assert_eq!(suggest_key(schema, "theme"), Some("themes"));
+        assert_eq!(suggest_key(schema, "completely_unrelated"), None);
+        assert_eq!(suggest_key(&Schema::Map(&Schema::Any), "grammer"), None);
+    }
+
+    #[test]
+    fn test_find_unknown_keys() {
+        let value = serde_json::json!({
+            "id": "example",
+            "grammer": {},
+            "language_servers": {
+                "example-ls": {
+                    "languages": ["Example"],
+                    "langauge_ids": {},
+                    "code_action_kinds": ["quickfix"],
+                },
+            },
+            "capabilities": [
+                { "kind": "process:exec", "command": "npm", "args": [] },
+                { "kind": "process:exec", "comand": "npm" },
+            ],
+            "grammars": {
+                "example": { "repository": "https://example.com", "rev": "abc", "subdir": "x" },
+            },
+        });
+
+        assert_eq!(
+            unknown_keys(&manifest_schema::EXTENSION_TOML, value),
+            vec![
+                ("capabilities[1].comand".to_string(), Some("command")),
+                ("grammars.example.subdir".to_string(), None),
+                ("grammer".to_string(), Some("grammars")),
+                (
+                    "language_servers.example-ls.langauge_ids".to_string(),
+                    Some("language_ids")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_unknown_keys_in_legacy_manifests() {
+        let value = serde_json::json!({
+            "name": "Example",
+            "version": "1.0.0",
+            "themes": { "Example": "themes/example.json" },
+            "grammars": { "example": "grammars/example.wasm" },
+            "languages": { "Example": "languages/example" },
+            "lib": { "kind": "Rust" },
+        });
+
+        assert_eq!(
+            unknown_keys(&manifest_schema::EXTENSION_JSON, value),
+            vec![("lib".to_string(), None)]
+        );
+    }
+
+    /// An `extension.toml` that uses every key Zed reads.
+    const FULL_EXTENSION_TOML: &str = r#"
+        id = "example"
+        name = "Example"
+        version = "1.0.0"
+        schema_version = 1
+        description = "An example extension"
+        repository = "https://github.com/example/example"
+        authors = ["Jane Doe <jane@example.com>"]
+        themes = ["themes/example.json"]
+        icon_themes = ["icon_themes/example.json"]
+        languages = ["languages/example"]
+        snippets = "snippets.json"
+
+        [lib]
+        kind = "Rust"
+        version = "0.1.0"
+
+        [grammars.example]
+        repository = "https://github.com/example/tree-sitter-example"
+        rev = "0123456789abcdef"
+        path = "grammars/example"
+
+        [grammars.legacy]
+        repository = "https://github.com/example/tree-sitter-legacy"
+        commit = "0123456789abcdef"
+
+        [language_servers.example-ls]
+        language = "Example"
+        languages = ["Example"]
+        language_ids = { Example = "example" }
+        code_action_kinds = ["quickfix"]
+
+        [context_servers.example-cs]
+
+        [slash_commands.example]
+        description = "An example command"
+        requires_argument = true
+
+        [indexed_docs_providers.example]
+
+        [[capabilities]]
+        kind = "process:exec"
+        command = "node"
+        args = ["**"]
+
+        [[capabilities]]
+        kind = "download_file"
+        host = "github.com"
+        path = ["example", "**"]
+
+        [[capabilities]]
+        kind = "npm:install"
+        package = "typescript"
+
+        [debug_adapters.example]
+        schema_path = "debug_adapter_schemas/example.json"
+
+        [debug_locators.example]
+
+        [agent_servers.example]
+        name = "Example Agent"
+        icon = "icons/example.svg"
+        env = { EXAMPLE = "1" }
+
+        [agent_servers.example.targets.darwin-aarch64]
+        archive = "https://example.com/agent.tar.gz"
+        cmd = "./agent"
+        args = ["--acp"]
+        sha256 = "0123456789abcdef"
+        env = { EXAMPLE = "1" }
+
+        [language_model_providers.example]
+    "#;
+
+    /// An `extension.json` that uses every key Zed reads.
+    const FULL_EXTENSION_JSON: &str = r#"{
+        "name": "Example",
+        "version": "1.0.0",
+        "description": "An example extension",
+        "repository": "https://github.com/example/example",
+        "authors": ["Jane Doe <jane@example.com>"],
+        "themes": { "Example": "themes/example.json" },
+        "languages": { "Example": "languages/example" },
+        "grammars": { "example": "grammars/example.wasm" }
+    }"#;
+
+    /// Guards against drift between the schemas and the manifest models in [`crate::extension`].
+    #[test]
+    fn test_schema_covers_extension_toml() {
+        let value = toml::from_str::<Value>(FULL_EXTENSION_TOML).unwrap();
+        assert_eq!(
+            unknown_keys(&manifest_schema::EXTENSION_TOML, value),
+            vec![]
+        );
+
+        // Every field of the model must be in the schema, too.
+        let manifest = toml::from_str::<ExtensionManifest>(FULL_EXTENSION_TOML).unwrap();
+        let value = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(
+            unknown_keys(&manifest_schema::EXTENSION_TOML, value),
+            vec![]
+        );
+    }
+
+    /// Guards against drift between the schema and the legacy manifest model in [`crate::extension`].
+    #[tokio::test]
+    async fn test_schema_covers_extension_json() {
+        let value = serde_json_lenient::from_str::<Value>(FULL_EXTENSION_JSON).unwrap();
+        assert_eq!(
+            unknown_keys(&manifest_schema::EXTENSION_JSON, value),
+            vec![]
+        );
+
+        // The legacy model is private, so load it the way surveys do and check that every key in
+        // the schema was read.
+        let extension_dir = std::env::temp_dir().join(format!(
+            "extension-surveyor-test-{}-example",
+            std::process::id()
+        ));
+        fs::create_dir_all(&extension_dir).await.unwrap();
+        fs::write(
+            extension_dir.join(ManifestFormat::Json.file_name()),
+            FULL_EXTENSION_JSON,
+        )
+        .await
+        .unwrap();
+        let loaded_manifest = ExtensionManifest::load(&extension_dir).await;
+        fs::remove_dir_all(&extension_dir).await.unwrap();
+
+        let manifest = loaded_manifest.unwrap().manifest;
+        assert_eq!(manifest.name, "Example");
+        assert_eq!(&*manifest.version, "1.0.0");
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("An example extension")
+        );
+        assert_eq!(
+            manifest.repository.as_deref(),
+            Some("https://github.com/example/example")
+        );
+        assert_eq!(manifest.authors, ["Jane Doe <jane@example.com>"]);
+        assert_eq!(manifest.themes, [PathBuf::from("themes/example.json")]);
+        assert_eq!(manifest.languages, [PathBuf::from("languages/example")]);
+        assert_eq!(
+            manifest
+                .grammars
+                .keys()
+                .map(|name| &**name)
+                .collect::<Vec<_>>(),
+            ["example"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_key_display_quotes_non_bare_keys() {
+        let unknown_key = UnknownKey {
+            path: vec![
+                PathSegment::Key("grammars".to_string()),
+                PathSegment::Key("my.grammar".to_string()),
+                PathSegment::Key("rev ".to_string()),
+            ],
+            suggestion: None,
+        };
+        assert_eq!(unknown_key.to_string(), r#"grammars."my.grammar"."rev ""#);
+    }
+}